@@ -0,0 +1,143 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::prelude::*;
+
+use crate::config::{
+    audio::AudioSettings,
+    settings::{settings_changed, Settings, SettingsSection},
+};
+
+/// A group of sounds sharing one volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioChannel {
+    Music,
+    Sfx,
+}
+
+/// Something that can actually make noise.
+///
+/// Volumes passed to the backend already include the master volume and mute state.
+pub trait AudioBackend: Send + Sync + 'static {
+    /// Sets the volume of a channel, `0.0..=1.0`.
+    fn set_volume(&mut self, channel: AudioChannel, volume: f32);
+    /// Starts playing the named sound on a channel.
+    fn play(&mut self, sound: &str, channel: AudioChannel);
+}
+
+/// A backend that discards everything, for machines without a sound device.
+#[derive(Debug, Default)]
+pub struct NullAudioBackend;
+
+impl AudioBackend for NullAudioBackend {
+    fn set_volume(&mut self, _channel: AudioChannel, _volume: f32) {}
+
+    fn play(&mut self, _sound: &str, _channel: AudioChannel) {}
+}
+
+/// A call made on an [`AudioBackend`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioCommand {
+    SetVolume { channel: AudioChannel, volume: f32 },
+    Play { sound: String, channel: AudioChannel },
+}
+
+/// A backend that records every call, so tests can inspect what would have been played.
+///
+/// Clones share the same record.
+#[derive(Debug, Default, Clone)]
+pub struct RecordingAudioBackend {
+    commands: Arc<Mutex<Vec<AudioCommand>>>,
+}
+
+impl RecordingAudioBackend {
+    pub fn commands(&self) -> Vec<AudioCommand> {
+        self.commands.lock().expect("audio record poisoned").clone()
+    }
+
+    fn record(&self, command: AudioCommand) {
+        self.commands.lock().expect("audio record poisoned").push(command);
+    }
+}
+
+impl AudioBackend for RecordingAudioBackend {
+    fn set_volume(&mut self, channel: AudioChannel, volume: f32) {
+        self.record(AudioCommand::SetVolume { channel, volume });
+    }
+
+    fn play(&mut self, sound: &str, channel: AudioChannel) {
+        self.record(AudioCommand::Play {
+            sound: sound.to_string(),
+            channel,
+        });
+    }
+}
+
+/// The audio output used by the app.
+#[derive(Resource)]
+pub struct Audio {
+    backend: Box<dyn AudioBackend>,
+}
+
+impl Audio {
+    pub fn new(backend: impl AudioBackend) -> Self {
+        Self {
+            backend: Box::new(backend),
+        }
+    }
+
+    pub fn play(&mut self, sound: &str, channel: AudioChannel) {
+        self.backend.play(sound, channel);
+    }
+
+    /// Pushes the effective channel volumes of `settings` to the backend.
+    pub fn apply_settings(&mut self, settings: &AudioSettings) {
+        for channel in [AudioChannel::Music, AudioChannel::Sfx] {
+            self.backend
+                .set_volume(channel, settings.effective_volume(channel));
+        }
+    }
+}
+
+/// Asks the [`Audio`] resource to play a sound.
+#[derive(Debug, Clone, PartialEq, Eq, Event)]
+pub struct PlaySound {
+    pub sound: String,
+    pub channel: AudioChannel,
+}
+
+/// Sets up the [`Audio`] resource and keeps it in sync with the [`AudioSettings`]
+/// of the [`Settings`].
+pub struct AudioPlugin {
+    /// Creates the backend, [`NullAudioBackend`] by default.
+    pub backend: fn() -> Audio,
+}
+
+impl Default for AudioPlugin {
+    fn default() -> Self {
+        Self {
+            backend: || Audio::new(NullAudioBackend),
+        }
+    }
+}
+
+impl Plugin for AudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource((self.backend)())
+            .add_event::<PlaySound>()
+            .add_systems(
+                Update,
+                apply_audio_settings.run_if(settings_changed(SettingsSection::Audio)),
+            )
+            .add_systems(Update, play_sounds.after(apply_audio_settings));
+    }
+}
+
+fn apply_audio_settings(settings: Res<Settings>, mut audio: ResMut<Audio>) {
+    audio.apply_settings(&settings.audio);
+}
+
+fn play_sounds(mut events: EventReader<PlaySound>, mut audio: ResMut<Audio>) {
+    for event in events.read() {
+        audio.play(&event.sound, event.channel);
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+use crate::audio::backend::AudioChannel;
+
+/// Highest volume, volumes are percentages.
+pub const MAX_VOLUME: u8 = 100;
+
+/// The `[audio]` section of the [`Settings`](crate::config::settings::Settings).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    pub master_volume: u8,
+    pub music_volume: u8,
+    pub sfx_volume: u8,
+    pub master_muted: bool,
+    pub music_muted: bool,
+    pub sfx_muted: bool,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master_volume: 80,
+            music_volume: 70,
+            sfx_volume: 100,
+            master_muted: false,
+            music_muted: false,
+            sfx_muted: false,
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Volume the backend should use for a channel, `0.0..=1.0`.
+    pub fn effective_volume(&self, channel: AudioChannel) -> f32 {
+        let (volume, muted) = match channel {
+            AudioChannel::Music => (self.music_volume, self.music_muted),
+            AudioChannel::Sfx => (self.sfx_volume, self.sfx_muted),
+        };
+        if self.master_muted || muted {
+            return 0.0;
+        }
+        let max = MAX_VOLUME as f32;
+        (self.master_volume as f32 / max) * (volume as f32 / max)
+    }
+
+    pub(crate) fn sanitized(mut self) -> Self {
+        self.master_volume = self.master_volume.min(MAX_VOLUME);
+        self.music_volume = self.music_volume.min(MAX_VOLUME);
+        self.sfx_volume = self.sfx_volume.min(MAX_VOLUME);
+        self
+    }
+}
@@ -0,0 +1,606 @@
+use std::{fmt::Debug, ops::RangeInclusive, time::Duration};
+
+use anyhow::{anyhow, bail, Context, Result};
+use bevy::prelude::*;
+use crossterm::event::{KeyCode, KeyModifiers, MediaKeyCode, ModifierKeyCode};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::settings::{detect_settings_changes, settings_changed, Settings, SettingsSection},
+    input::routing::InputRoutingSet,
+    widgets::key_bindings::{
+        KeyBinding, KeyBindingsTrait, KeyBindingsWidget, KeyChord, KeySequence,
+    },
+};
+
+/// Time to press the next chord of a sequence such as `g g`, unless configured.
+pub const DEFAULT_SEQUENCE_TIMEOUT_MS: u64 = 1000;
+const SEQUENCE_TIMEOUT_RANGE_MS: RangeInclusive<u64> = 100..=5000;
+
+/// Builds the [`KeyBindingsWidget`] from the controls in the [`Settings`]
+/// whenever they change.
+pub struct KeyBindingsConfigPlugin;
+
+impl Plugin for KeyBindingsConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<KeyBindingsWidget>().add_systems(
+            PreUpdate,
+            apply_controls
+                .after(InputRoutingSet::Handle)
+                .after(detect_settings_changes)
+                .run_if(settings_changed(SettingsSection::Controls)),
+        );
+    }
+}
+
+/// The `[controls]` section of the [`Settings`], the key bindings as written to the file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControlSettings {
+    /// How long a started key sequence waits for its next chord.
+    pub sequence_timeout_ms: u64,
+    #[serde(rename = "binding")]
+    pub bindings: Vec<KeyBindingEntry>,
+    /// Profiles saved by the player, the [`BuiltinProfile`]s are not written.
+    #[serde(rename = "profile", skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<KeyBindingProfile>,
+}
+
+impl Default for ControlSettings {
+    fn default() -> Self {
+        Self {
+            sequence_timeout_ms: DEFAULT_SEQUENCE_TIMEOUT_MS,
+            bindings: BuiltinProfile::default().bindings(),
+            profiles: Vec::new(),
+        }
+    }
+}
+
+impl ControlSettings {
+    /// The bindings of `widget` in a stable order, so the file diffs nicely.
+    pub fn from_widget(widget: &KeyBindingsWidget) -> Self {
+        let bindings = widget
+            .key_bindings
+            .values()
+            .map(KeyBindingEntry::from_key_binding)
+            .collect();
+        Self {
+            bindings,
+            ..Default::default()
+        }
+        .sorted()
+    }
+
+    /// Puts the bindings in the order [`ControlSettings::from_widget`] writes them,
+    /// so loaded and edited controls compare equal when they hold the same bindings.
+    pub(crate) fn sorted(mut self) -> Self {
+        self.bindings = sorted_entries(self.bindings);
+        self
+    }
+
+    pub(crate) fn sanitized(mut self) -> Self {
+        self.sequence_timeout_ms = self.sequence_timeout_ms.clamp(
+            *SEQUENCE_TIMEOUT_RANGE_MS.start(),
+            *SEQUENCE_TIMEOUT_RANGE_MS.end(),
+        );
+        self.sorted()
+    }
+
+    pub fn sequence_timeout(&self) -> Duration {
+        Duration::from_millis(self.sequence_timeout_ms)
+    }
+
+    /// Reads a `[controls]` table.
+    ///
+    /// Parsed loosely so a single bad entry does not reject the whole section,
+    /// malformed entries are skipped and described in the returned errors.
+    /// Without any bindings the default profile is used.
+    pub fn parse(value: toml::Value) -> Result<(Self, Vec<String>)> {
+        #[derive(Deserialize)]
+        struct RawControls {
+            sequence_timeout_ms: Option<u64>,
+            #[serde(default)]
+            binding: Vec<toml::Value>,
+            #[serde(default)]
+            profile: Vec<toml::Value>,
+        }
+
+        #[derive(Deserialize)]
+        struct RawProfile {
+            name: String,
+            #[serde(default)]
+            binding: Vec<toml::Value>,
+        }
+
+        let raw: RawControls = value.try_into()?;
+        let mut controls = ControlSettings::default();
+        let mut errors = Vec::new();
+        if let Some(timeout) = raw.sequence_timeout_ms {
+            controls.sequence_timeout_ms = timeout;
+        }
+        if !raw.binding.is_empty() {
+            controls.bindings = parse_entries(raw.binding, &mut errors);
+        }
+        for (index, value) in raw.profile.into_iter().enumerate() {
+            let profile = match value.try_into::<RawProfile>() {
+                Ok(profile) => profile,
+                Err(err) => {
+                    let message = format!("profile #{}: {}", index + 1, err);
+                    errors.push(message.trim_end().to_string());
+                    continue;
+                }
+            };
+            if let Some(builtin) = BuiltinProfile::from_name(&profile.name) {
+                errors.push(format!(
+                    "profile `{}`: named like the built-in profile `{}`",
+                    profile.name,
+                    builtin.name()
+                ));
+                continue;
+            }
+            if controls.find_profile(&profile.name).is_some() {
+                errors.push(format!("profile `{}`: defined twice", profile.name));
+                continue;
+            }
+            let mut profile_errors = Vec::new();
+            let bindings = parse_entries(profile.binding, &mut profile_errors);
+            errors.extend(
+                profile_errors
+                    .into_iter()
+                    .map(|err| format!("profile `{}`: {}", profile.name, err)),
+            );
+            controls.profiles.push(KeyBindingProfile {
+                name: profile.name,
+                bindings,
+            });
+        }
+        Ok((controls, errors))
+    }
+
+    /// Names of the profiles to pick from, the built-in ones first.
+    pub fn profile_names(&self) -> Vec<String> {
+        BuiltinProfile::ALL
+            .iter()
+            .map(|profile| profile.name().to_string())
+            .chain(self.profiles.iter().map(|profile| profile.name.clone()))
+            .collect()
+    }
+
+    /// The bindings of the profile called `name`.
+    ///
+    /// Names are case-insensitive, for the built-in profiles as for the user ones.
+    pub fn profile(&self, name: &str) -> Option<Vec<KeyBindingEntry>> {
+        if let Some(builtin) = BuiltinProfile::from_name(name) {
+            return Some(builtin.bindings());
+        }
+        self.find_profile(name).map(|profile| profile.bindings.clone())
+    }
+
+    fn find_profile(&self, name: &str) -> Option<&KeyBindingProfile> {
+        self.profiles
+            .iter()
+            .find(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The profile the current bindings match, `None` once they were changed.
+    pub fn active_profile(&self) -> Option<String> {
+        let current = sorted_entries(self.bindings.clone());
+        self.profile_names().into_iter().find(|name| {
+            self.profile(name)
+                .is_some_and(|bindings| sorted_entries(bindings) == current)
+        })
+    }
+
+    /// Switches to the profile after the active one, or to the first profile.
+    ///
+    /// Returns the name of the profile now in use.
+    pub fn use_next_profile(&mut self) -> String {
+        let names = self.profile_names();
+        let next = self
+            .active_profile()
+            .and_then(|active| names.iter().position(|name| *name == active))
+            .map_or(0, |index| (index + 1) % names.len());
+        let name = names[next].clone();
+        self.bindings = sorted_entries(self.profile(&name).unwrap_or_default());
+        name
+    }
+
+    /// Saves the current bindings as a user profile, replacing one of the same name.
+    pub fn save_profile(&mut self, name: &str) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("A profile needs a name");
+        }
+        if BuiltinProfile::from_name(name).is_some() {
+            bail!("'{}' is a built-in profile", name);
+        }
+        let profile = KeyBindingProfile {
+            name: name.to_string(),
+            bindings: self.bindings.clone(),
+        };
+        match self
+            .profiles
+            .iter_mut()
+            .find(|existing| existing.name.eq_ignore_ascii_case(name))
+        {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+        Ok(())
+    }
+
+    /// Builds the bindings, skipping the ones with unknown keys or a key bound twice.
+    pub fn to_key_bindings(&self) -> LoadedKeyBindings {
+        let mut loaded = LoadedKeyBindings {
+            widget: KeyBindingsWidget::new(),
+            errors: Vec::new(),
+        };
+        // named rather than numbered, entries that failed to parse are not in the list
+        for entry in &self.bindings {
+            let key_binding = entry
+                .to_key_binding()
+                .and_then(|key_binding| loaded.widget.add_custom_key_binding(key_binding));
+            if let Err(err) = key_binding {
+                let message = format!("binding `{}` on `{}`: {:#}", entry.action, entry.user_key, err);
+                loaded.errors.push(message.trim_end().to_string());
+            }
+        }
+        loaded
+    }
+}
+
+fn sorted_entries(mut bindings: Vec<KeyBindingEntry>) -> Vec<KeyBindingEntry> {
+    bindings.sort_by(|a, b| a.action.cmp(&b.action).then(a.user_key.cmp(&b.user_key)));
+    bindings
+}
+
+fn parse_entries(values: Vec<toml::Value>, errors: &mut Vec<String>) -> Vec<KeyBindingEntry> {
+    let mut bindings = Vec::new();
+    for (index, value) in values.into_iter().enumerate() {
+        match value.try_into::<KeyBindingEntry>() {
+            Ok(entry) => bindings.push(entry),
+            Err(err) => errors.push(binding_error(index, &err.into())),
+        }
+    }
+    bindings
+}
+
+fn binding_error(index: usize, err: &anyhow::Error) -> String {
+    let message = format!("binding #{}: {:#}", index + 1, err);
+    message.trim_end().to_string()
+}
+
+/// A set of bindings saved under a name, a `[[controls.profile]]` table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBindingProfile {
+    pub name: String,
+    #[serde(rename = "binding", default)]
+    pub bindings: Vec<KeyBindingEntry>,
+}
+
+/// The layouts that come with the game.
+///
+/// They differ in the keys used to move, the other bindings are shared.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinProfile {
+    #[default]
+    Arrows,
+    Wasd,
+    Vim,
+}
+
+impl BuiltinProfile {
+    pub const ALL: [BuiltinProfile; 3] =
+        [BuiltinProfile::Arrows, BuiltinProfile::Wasd, BuiltinProfile::Vim];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltinProfile::Arrows => "Arrows",
+            BuiltinProfile::Wasd => "WASD",
+            BuiltinProfile::Vim => "Vim",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|profile| profile.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Keys for up, down, left and right.
+    fn movement_keys(&self) -> [KeyCode; 4] {
+        match self {
+            BuiltinProfile::Arrows => [KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right],
+            BuiltinProfile::Wasd => ['w', 's', 'a', 'd'].map(KeyCode::Char),
+            BuiltinProfile::Vim => ['k', 'j', 'h', 'l'].map(KeyCode::Char),
+        }
+    }
+
+    /// A binding for every system key. Esc and Enter cannot be moved, so the
+    /// menus stay usable whatever the player does.
+    pub fn bindings(&self) -> Vec<KeyBindingEntry> {
+        let binding = |action: &str, user_key, system_key, customizable| {
+            KeyBindingEntry::from_key_binding(&KeyBinding {
+                defined_action_description: action.to_string(),
+                user_key,
+                system_key,
+                is_customizable: customizable,
+                ..Default::default()
+            })
+        };
+        let [up, down, left, right] = self.movement_keys();
+        sorted_entries(vec![
+            binding("Back / Options", KeyCode::Esc, KeyCode::Esc, false),
+            binding("Confirm", KeyCode::Enter, KeyCode::Enter, false),
+            binding("Move up", up, KeyCode::Up, true),
+            binding("Move down", down, KeyCode::Down, true),
+            binding("Move left", left, KeyCode::Left, true),
+            binding("Move right", right, KeyCode::Right, true),
+            binding("Quit", KeyCode::Char('q'), KeyCode::Char('q'), true),
+        ])
+    }
+}
+
+/// A single `[[controls.binding]]` table. Keys are written as chords like `Ctrl+Shift+s`,
+/// the user key may also be a sequence of chords like `Ctrl+x Ctrl+s`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyBindingEntry {
+    pub action: String,
+    pub user_key: String,
+    pub system_key: String,
+    #[serde(default)]
+    pub customizable: bool,
+}
+
+impl KeyBindingEntry {
+    pub fn from_key_binding(key_binding: &KeyBinding) -> Self {
+        Self {
+            action: key_binding.defined_action_description.clone(),
+            user_key: format_key_sequence(&key_binding.user_sequence()),
+            system_key: format_key_chord(key_binding.system_key, key_binding.system_key_modifiers),
+            customizable: key_binding.is_customizable,
+        }
+    }
+
+    pub fn to_key_binding(&self) -> Result<KeyBinding> {
+        let user_key = parse_key_sequence(&self.user_key).context("invalid user_key")?;
+        let (system_key, system_key_modifiers) =
+            parse_key_chord(&self.system_key).context("invalid system_key")?;
+        let mut key_binding = KeyBinding {
+            defined_action_description: self.action.clone(),
+            system_key,
+            system_key_modifiers,
+            is_customizable: self.customizable,
+            ..Default::default()
+        };
+        key_binding.set_user_sequence(&user_key);
+        Ok(key_binding)
+    }
+}
+
+/// Result of reading key bindings.
+///
+/// Malformed entries are skipped and described in `errors` so the
+/// remaining bindings can still be used.
+#[derive(Debug)]
+pub struct LoadedKeyBindings {
+    pub widget: KeyBindingsWidget,
+    pub errors: Vec<String>,
+}
+
+/// Parses `[[binding]]` tables, the layout of the `[controls]` section.
+pub fn parse_key_bindings(contents: &str) -> Result<LoadedKeyBindings> {
+    let (controls, mut errors) = ControlSettings::parse(toml::from_str(contents)?)?;
+    let mut loaded = controls.to_key_bindings();
+    errors.append(&mut loaded.errors);
+    loaded.errors = errors;
+    Ok(loaded)
+}
+
+/// Swaps in the bindings from the settings, keeping the table's own state.
+fn apply_controls(settings: Res<Settings>, mut key_bindings: ResMut<KeyBindingsWidget>) {
+    let loaded = settings.controls.to_key_bindings();
+    for err in &loaded.errors {
+        warn!("Skipping malformed key binding: {}", err);
+    }
+    key_bindings.key_bindings = loaded.widget.key_bindings;
+}
+
+/// Formats a sequence as its chords separated by spaces, e.g. `Ctrl+x Ctrl+s`.
+///
+/// This is the inverse of [`parse_key_sequence`].
+pub fn format_key_sequence(sequence: &KeySequence) -> String {
+    let chords: Vec<String> = sequence
+        .chords()
+        .iter()
+        .map(|(code, modifiers)| format_key_chord(*code, *modifiers))
+        .collect();
+    chords.join(" ")
+}
+
+/// Parses chords separated by spaces, such as `g g` or `Ctrl+x Ctrl+s`.
+///
+/// A single chord is a sequence of one. The space bar is written as `Space`.
+pub fn parse_key_sequence(sequence: &str) -> Result<KeySequence> {
+    let chords = sequence
+        .split_whitespace()
+        .map(parse_key_chord)
+        .collect::<Result<Vec<KeyChord>>>()?;
+    KeySequence::new(chords).ok_or_else(|| anyhow!("empty key chord"))
+}
+
+/// Formats a key and its modifiers as a chord such as `Ctrl+Alt+Delete`.
+///
+/// This is the inverse of [`parse_key_chord`].
+pub fn format_key_chord(code: KeyCode, modifiers: KeyModifiers) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for (flag, name) in MODIFIER_NAMES {
+        if modifiers.contains(*flag) {
+            parts.push(name.to_string());
+        }
+    }
+    let key = match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        KeyCode::Media(media) => format!("Media({})", key_name(MEDIA_KEYS, media)),
+        KeyCode::Modifier(modifier) => format!("Modifier({})", key_name(MODIFIER_KEYS, modifier)),
+        named => NAMED_KEYS
+            .iter()
+            .find(|(key, _)| *key == named)
+            .map(|(_, name)| name.to_string())
+            .unwrap_or_else(|| format!("{:?}", named)),
+    };
+    parts.push(key);
+    parts.join("+")
+}
+
+/// Parses a chord such as `Ctrl+Shift+s`, `Esc` or `Alt++`.
+///
+/// Modifier and key names are case-insensitive, except single characters
+/// which are kept as typed.
+pub fn parse_key_chord(chord: &str) -> Result<(KeyCode, KeyModifiers)> {
+    let chord = chord.trim();
+    if chord.is_empty() {
+        bail!("empty key chord");
+    }
+    // A trailing `+` is the plus key itself, e.g. `Ctrl++`.
+    let (prefix, key) = if chord == "+" {
+        ("", "+")
+    } else if let Some(prefix) = chord.strip_suffix("++") {
+        (prefix, "+")
+    } else {
+        match chord.rsplit_once('+') {
+            Some((prefix, key)) => (prefix, key),
+            None => ("", chord),
+        }
+    };
+
+    let mut modifiers = KeyModifiers::empty();
+    if !prefix.is_empty() {
+        for part in prefix.split('+') {
+            let flag = MODIFIER_NAMES
+                .iter()
+                .find(|(_, name)| name.eq_ignore_ascii_case(part.trim()))
+                .map(|(flag, _)| *flag)
+                .ok_or_else(|| anyhow!("unknown modifier `{}` in `{}`", part, chord))?;
+            modifiers |= flag;
+        }
+    }
+
+    Ok((parse_key_code(key.trim()).with_context(|| format!("in `{}`", chord))?, modifiers))
+}
+
+fn parse_key_code(key: &str) -> Result<KeyCode> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(c));
+    }
+    if key.eq_ignore_ascii_case("Space") {
+        return Ok(KeyCode::Char(' '));
+    }
+    if let Some((code, _)) = NAMED_KEYS
+        .iter()
+        .find(|(_, name)| name.eq_ignore_ascii_case(key))
+    {
+        return Ok(*code);
+    }
+    if let Some(n) = key.strip_prefix(['F', 'f']).and_then(|n| n.parse::<u8>().ok()) {
+        return Ok(KeyCode::F(n));
+    }
+    if let Some(media) = wrapped_key(key, "Media", MEDIA_KEYS) {
+        return Ok(KeyCode::Media(media));
+    }
+    if let Some(modifier) = wrapped_key(key, "Modifier", MODIFIER_KEYS) {
+        return Ok(KeyCode::Modifier(modifier));
+    }
+    bail!("unknown key `{}`", key)
+}
+
+/// Looks up the name in `Media(PlayPause)` style keys.
+fn wrapped_key<T: Copy>(key: &str, kind: &str, names: &[(T, &str)]) -> Option<T> {
+    let prefix = key.get(..kind.len() + 1)?;
+    if !prefix.eq_ignore_ascii_case(&format!("{}(", kind)) {
+        return None;
+    }
+    let name = key[kind.len() + 1..].strip_suffix(')')?;
+    names
+        .iter()
+        .find(|(_, known)| known.eq_ignore_ascii_case(name.trim()))
+        .map(|(key, _)| *key)
+}
+
+fn key_name<T: PartialEq + Debug>(names: &[(T, &str)], key: T) -> String {
+    names
+        .iter()
+        .find(|(known, _)| *known == key)
+        .map_or_else(|| format!("{:?}", key), |(_, name)| name.to_string())
+}
+
+const MODIFIER_NAMES: &[(KeyModifiers, &str)] = &[
+    (KeyModifiers::CONTROL, "Ctrl"),
+    (KeyModifiers::ALT, "Alt"),
+    (KeyModifiers::SHIFT, "Shift"),
+    (KeyModifiers::SUPER, "Super"),
+    (KeyModifiers::HYPER, "Hyper"),
+    (KeyModifiers::META, "Meta"),
+];
+
+const NAMED_KEYS: &[(KeyCode, &str)] = &[
+    (KeyCode::Null, "Null"),
+    (KeyCode::Enter, "Enter"),
+    (KeyCode::Esc, "Esc"),
+    (KeyCode::Backspace, "Backspace"),
+    (KeyCode::Left, "Left"),
+    (KeyCode::Right, "Right"),
+    (KeyCode::Up, "Up"),
+    (KeyCode::Down, "Down"),
+    (KeyCode::Home, "Home"),
+    (KeyCode::End, "End"),
+    (KeyCode::PageUp, "PageUp"),
+    (KeyCode::PageDown, "PageDown"),
+    (KeyCode::Tab, "Tab"),
+    (KeyCode::BackTab, "BackTab"),
+    (KeyCode::Delete, "Delete"),
+    (KeyCode::Insert, "Insert"),
+    (KeyCode::NumLock, "NumLock"),
+    (KeyCode::ScrollLock, "ScrollLock"),
+    (KeyCode::CapsLock, "CapsLock"),
+    (KeyCode::PrintScreen, "PrintScreen"),
+    (KeyCode::Pause, "Pause"),
+    (KeyCode::Menu, "Menu"),
+    (KeyCode::KeypadBegin, "KeypadBegin"),
+];
+
+const MEDIA_KEYS: &[(MediaKeyCode, &str)] = &[
+    (MediaKeyCode::Play, "Play"),
+    (MediaKeyCode::Pause, "Pause"),
+    (MediaKeyCode::PlayPause, "PlayPause"),
+    (MediaKeyCode::Reverse, "Reverse"),
+    (MediaKeyCode::Stop, "Stop"),
+    (MediaKeyCode::FastForward, "FastForward"),
+    (MediaKeyCode::Rewind, "Rewind"),
+    (MediaKeyCode::TrackNext, "TrackNext"),
+    (MediaKeyCode::TrackPrevious, "TrackPrevious"),
+    (MediaKeyCode::Record, "Record"),
+    (MediaKeyCode::LowerVolume, "LowerVolume"),
+    (MediaKeyCode::RaiseVolume, "RaiseVolume"),
+    (MediaKeyCode::MuteVolume, "MuteVolume"),
+];
+
+const MODIFIER_KEYS: &[(ModifierKeyCode, &str)] = &[
+    (ModifierKeyCode::LeftShift, "LeftShift"),
+    (ModifierKeyCode::LeftControl, "LeftControl"),
+    (ModifierKeyCode::LeftAlt, "LeftAlt"),
+    (ModifierKeyCode::LeftSuper, "LeftSuper"),
+    (ModifierKeyCode::LeftHyper, "LeftHyper"),
+    (ModifierKeyCode::LeftMeta, "LeftMeta"),
+    (ModifierKeyCode::RightShift, "RightShift"),
+    (ModifierKeyCode::RightControl, "RightControl"),
+    (ModifierKeyCode::RightAlt, "RightAlt"),
+    (ModifierKeyCode::RightSuper, "RightSuper"),
+    (ModifierKeyCode::RightHyper, "RightHyper"),
+    (ModifierKeyCode::RightMeta, "RightMeta"),
+    (ModifierKeyCode::IsoLevel3Shift, "IsoLevel3Shift"),
+    (ModifierKeyCode::IsoLevel5Shift, "IsoLevel5Shift"),
+];
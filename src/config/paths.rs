@@ -0,0 +1,8 @@
+use std::path::PathBuf;
+
+const CONFIG_DIR_NAME: &str = "bevy_starter";
+
+/// Path of a file in the app's config directory, if the platform has one.
+pub fn config_file(file_name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(CONFIG_DIR_NAME).join(file_name))
+}
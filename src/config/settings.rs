@@ -0,0 +1,429 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    config::{
+        audio::AudioSettings, key_bindings::ControlSettings, paths, theme::ThemeSettings,
+        toml_file, video::VideoSettings,
+    },
+    input::routing::InputRoutingSet,
+};
+
+const SETTINGS_FILE_NAME: &str = "settings.toml";
+
+/// Files the settings were split over before `settings.toml`, with the section
+/// each one becomes. They are read once, when there is no `settings.toml` yet.
+const SPLIT_FILES: [(&str, &str); 3] = [
+    ("video", "video.toml"),
+    ("audio", "audio.toml"),
+    ("controls", "key_bindings.toml"),
+];
+
+/// Version written to `settings.toml`.
+///
+/// Bump it together with a new entry in [`MIGRATIONS`] whenever a setting is
+/// renamed or moved, so files written by older versions keep working.
+pub const SETTINGS_VERSION: u32 = 1;
+
+/// Upgrades the raw settings table by one version.
+type Migration = fn(&mut toml::Table) -> Result<()>;
+
+/// `MIGRATIONS[n]` upgrades version `n` to `n + 1`.
+const MIGRATIONS: [Migration; SETTINGS_VERSION as usize] = [migrate_split_files];
+
+/// Everything the player can configure, saved to `settings.toml`.
+///
+/// Fields missing from the file keep their defaults. Systems that depend on a
+/// section run on [`SettingsChanged`] instead of watching the whole resource.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub video: VideoSettings,
+    pub audio: AudioSettings,
+    pub controls: ControlSettings,
+    pub accessibility: AccessibilitySettings,
+    pub theme: ThemeSettings,
+}
+
+impl Settings {
+    /// Sections that differ between `self` and `other`.
+    pub fn changed_sections(&self, other: &Settings) -> Vec<SettingsSection> {
+        SettingsSection::ALL
+            .into_iter()
+            .filter(|section| match section {
+                SettingsSection::Video => self.video != other.video,
+                SettingsSection::Audio => self.audio != other.audio,
+                SettingsSection::Controls => self.controls != other.controls,
+                SettingsSection::Accessibility => self.accessibility != other.accessibility,
+                SettingsSection::Theme => self.theme != other.theme,
+            })
+            .collect()
+    }
+
+    /// Takes the `sections` from `other`, leaving the rest as it is.
+    pub fn copy_sections(&mut self, other: &Settings, sections: &[SettingsSection]) {
+        for section in sections {
+            match section {
+                SettingsSection::Video => self.video = other.video.clone(),
+                SettingsSection::Audio => self.audio = other.audio.clone(),
+                SettingsSection::Controls => self.controls = other.controls.clone(),
+                SettingsSection::Accessibility => {
+                    self.accessibility = other.accessibility.clone()
+                }
+                SettingsSection::Theme => self.theme = other.theme.clone(),
+            }
+        }
+    }
+
+    /// Whether the cursor may blink, reduced motion keeps it steady.
+    pub fn cursor_blink(&self) -> bool {
+        self.video.cursor_blink && !self.accessibility.reduced_motion
+    }
+
+    fn sanitized(mut self) -> Self {
+        self.video = self.video.sanitized();
+        self.audio = self.audio.sanitized();
+        self.controls = self.controls.sanitized();
+        self
+    }
+}
+
+/// The `[accessibility]` section of the [`Settings`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilitySettings {
+    /// Keeps the cursor from blinking.
+    pub reduced_motion: bool,
+    /// Draws with the high contrast scheme, whichever scheme is picked.
+    pub high_contrast: bool,
+}
+
+/// A part of the [`Settings`], see [`SettingsChanged`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettingsSection {
+    Video,
+    Audio,
+    Controls,
+    Accessibility,
+    Theme,
+}
+
+impl SettingsSection {
+    pub const ALL: [SettingsSection; 5] = [
+        SettingsSection::Video,
+        SettingsSection::Audio,
+        SettingsSection::Controls,
+        SettingsSection::Accessibility,
+        SettingsSection::Theme,
+    ];
+}
+
+/// Sent for each section of the [`Settings`] that changed, and for every section
+/// on the first frame so the loaded settings get applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct SettingsChanged {
+    pub section: SettingsSection,
+}
+
+/// Run condition for systems that apply a section of the [`Settings`].
+///
+/// Order them after [`detect_settings_changes`] to react in the same frame.
+pub fn settings_changed(
+    section: SettingsSection,
+) -> impl FnMut(EventReader<SettingsChanged>) -> bool + Clone {
+    move |mut events: EventReader<SettingsChanged>| {
+        // read every event, so none are left over for the next frame
+        let mut changed = false;
+        for event in events.read() {
+            changed |= event.section == section;
+        }
+        changed
+    }
+}
+
+/// Like [`settings_changed`], for systems that depend on several sections.
+pub fn settings_changed_any(
+    sections: &'static [SettingsSection],
+) -> impl FnMut(EventReader<SettingsChanged>) -> bool + Clone {
+    move |mut events: EventReader<SettingsChanged>| {
+        // read every event, so none are left over for the next frame
+        let mut changed = false;
+        for event in events.read() {
+            changed |= sections.contains(&event.section);
+        }
+        changed
+    }
+}
+
+/// Loads the [`Settings`] at startup, sends [`SettingsChanged`] when they change
+/// and writes them back.
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsPath>()
+            .add_event::<SettingsChanged>()
+            .add_systems(PreStartup, load_settings)
+            .add_systems(
+                PreUpdate,
+                detect_settings_changes.after(InputRoutingSet::Handle),
+            )
+            .add_systems(Last, save_settings);
+    }
+}
+
+/// Location of the settings file. `None` disables persistence.
+#[derive(Debug, Clone, Resource)]
+pub struct SettingsPath(pub Option<PathBuf>);
+
+impl Default for SettingsPath {
+    fn default() -> Self {
+        Self(paths::config_file(SETTINGS_FILE_NAME))
+    }
+}
+
+/// Result of reading the settings.
+#[derive(Debug)]
+pub struct LoadedSettings {
+    pub settings: Settings,
+    /// Version the settings were read as, older versions have been migrated.
+    pub version: u32,
+    /// Malformed settings and key bindings that were skipped.
+    pub errors: Vec<String>,
+}
+
+impl LoadedSettings {
+    pub fn was_migrated(&self) -> bool {
+        self.version < SETTINGS_VERSION
+    }
+}
+
+/// On-disk layout: the version first, then the sections.
+#[derive(Serialize)]
+struct SettingsFile<'a> {
+    version: u32,
+    #[serde(flatten)]
+    settings: &'a Settings,
+}
+
+/// Parses the contents of a settings file, migrating it from older versions.
+///
+/// A file without a version is taken to be of the current version.
+pub fn parse_settings(contents: &str) -> Result<LoadedSettings> {
+    settings_from_table(toml::from_str(contents)?)
+}
+
+fn settings_from_table(mut table: toml::Table) -> Result<LoadedSettings> {
+    let version = match table.remove("version") {
+        Some(toml::Value::Integer(version)) => {
+            u32::try_from(version).with_context(|| format!("invalid version {}", version))?
+        }
+        Some(other) => bail!("invalid version `{}`", other),
+        None => SETTINGS_VERSION,
+    };
+    if version > SETTINGS_VERSION {
+        bail!(
+            "version {} was written by a newer release, this one reads up to version {}",
+            version,
+            SETTINGS_VERSION
+        );
+    }
+    for (from, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(&mut table).with_context(|| format!("migrating from version {}", from))?;
+    }
+
+    // a bad value only resets itself, not the whole file
+    let mut errors = Vec::new();
+    let video = parse_section(&table, "video", &mut errors);
+    let audio = parse_section(&table, "audio", &mut errors);
+    let controls = match table.remove("controls").map(ControlSettings::parse) {
+        Some(Ok((controls, mut binding_errors))) => {
+            errors.append(&mut binding_errors);
+            controls
+        }
+        Some(Err(err)) => {
+            errors.push(format!("[controls]: {:#}", err).trim_end().to_string());
+            ControlSettings::default()
+        }
+        None => ControlSettings::default(),
+    };
+    let settings = Settings {
+        video,
+        audio,
+        controls,
+        accessibility: parse_section(&table, "accessibility", &mut errors),
+        theme: parse_section(&table, "theme", &mut errors),
+    };
+    Ok(LoadedSettings {
+        settings: settings.sanitized(),
+        version,
+        errors,
+    })
+}
+
+/// Reads the section `name` of the settings table.
+///
+/// Fields that don't fit keep their defaults and are described in `errors`.
+fn parse_section<T: DeserializeOwned + Default>(
+    table: &toml::Table,
+    name: &str,
+    errors: &mut Vec<String>,
+) -> T {
+    let Some(value) = table.get(name) else {
+        return T::default();
+    };
+    if let Ok(section) = value.clone().try_into() {
+        return section;
+    }
+    let toml::Value::Table(fields) = value else {
+        errors.push(format!("[{}]: expected a table, found `{}`", name, value));
+        return T::default();
+    };
+    let mut accepted = toml::Table::new();
+    for (key, field) in fields {
+        let mut candidate = accepted.clone();
+        candidate.insert(key.clone(), field.clone());
+        match toml::Value::Table(candidate.clone()).try_into::<T>() {
+            Ok(_) => accepted = candidate,
+            Err(err) => errors.push(format!("[{}] {}: {}", name, key, err.message().trim_end())),
+        }
+    }
+    toml::Value::Table(accepted).try_into().unwrap_or_default()
+}
+
+/// Version 0 is the layout of the separate files: the scheme name was part of
+/// the video settings.
+fn migrate_split_files(table: &mut toml::Table) -> Result<()> {
+    let Some(toml::Value::Table(video)) = table.get_mut("video") else {
+        return Ok(());
+    };
+    if let Some(name) = video.remove("theme") {
+        let mut theme = toml::Table::new();
+        theme.insert("name".to_string(), name);
+        table.insert("theme".to_string(), toml::Value::Table(theme));
+    }
+    Ok(())
+}
+
+/// Reads the settings file at `path`.
+///
+/// Without one, the separate files of version 0 next to it are read instead.
+/// Without those either, the defaults are used.
+pub fn load_settings_file(path: &Path) -> Result<LoadedSettings> {
+    if path.exists() {
+        let table = toml_file::load::<toml::Table>(path)?;
+        return settings_from_table(table).with_context(|| format!("in {}", path.display()));
+    }
+
+    let mut table = toml::Table::new();
+    for (section, file_name) in SPLIT_FILES {
+        let split_path = path.with_file_name(file_name);
+        if split_path.exists() {
+            table.insert(
+                section.to_string(),
+                toml::Value::Table(toml_file::load(&split_path)?),
+            );
+        }
+    }
+    if table.is_empty() {
+        return Ok(LoadedSettings {
+            settings: Settings::default(),
+            version: SETTINGS_VERSION,
+            errors: Vec::new(),
+        });
+    }
+    table.insert("version".to_string(), toml::Value::Integer(0));
+    settings_from_table(table).context("in the separate settings files")
+}
+
+/// Writes the settings file at `path` with the current version.
+pub fn save_settings_file(path: &Path, settings: &Settings) -> Result<()> {
+    toml_file::save(
+        path,
+        &SettingsFile {
+            version: SETTINGS_VERSION,
+            settings,
+        },
+    )
+}
+
+fn load_settings(mut commands: Commands, config_path: Res<SettingsPath>) {
+    let Some(path) = config_path.0.as_deref() else {
+        commands.insert_resource(Settings::default());
+        return;
+    };
+    let settings = match load_settings_file(path) {
+        Ok(loaded) => {
+            for err in &loaded.errors {
+                warn!("Skipping malformed setting in {}: {}", path.display(), err);
+            }
+            if loaded.was_migrated() {
+                info!(
+                    "Migrating settings from version {} to {}",
+                    loaded.version, SETTINGS_VERSION
+                );
+                if let Err(err) = save_settings_file(path, &loaded.settings) {
+                    error!("Failed to save migrated settings: {:#}", err);
+                }
+            }
+            loaded.settings
+        }
+        Err(err) => {
+            error!("Failed to load settings: {:#}", err);
+            back_up_settings_file(path);
+            Settings::default()
+        }
+    };
+    commands.insert_resource(settings);
+}
+
+/// Copies an unreadable settings file to `settings.toml.bak`, it is replaced
+/// by the defaults on the next save.
+fn back_up_settings_file(path: &Path) {
+    if !path.exists() {
+        return;
+    }
+    let backup = path.with_extension("toml.bak");
+    match std::fs::copy(path, &backup) {
+        Ok(_) => warn!("Kept the unreadable settings as {}", backup.display()),
+        Err(err) => error!("Failed to back up {}: {}", path.display(), err),
+    }
+}
+
+/// Compares the [`Settings`] with the last frame and sends a [`SettingsChanged`]
+/// per section that differs.
+pub fn detect_settings_changes(
+    settings: Res<Settings>,
+    mut previous: Local<Option<Settings>>,
+    mut changed: EventWriter<SettingsChanged>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let sections = match previous.as_ref() {
+        Some(previous) => settings.changed_sections(previous),
+        None => SettingsSection::ALL.to_vec(),
+    };
+    for section in sections {
+        changed.send(SettingsChanged { section });
+    }
+    *previous = Some(settings.clone());
+}
+
+fn save_settings(settings: Option<Res<Settings>>, config_path: Res<SettingsPath>) {
+    let Some(settings) = settings else {
+        return;
+    };
+    if !settings.is_changed() || settings.is_added() {
+        return;
+    }
+    let Some(path) = config_path.0.as_deref() else {
+        return;
+    };
+    if let Err(err) = save_settings_file(path, &settings) {
+        error!("Failed to save settings: {:#}", err);
+    }
+}
@@ -0,0 +1,248 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::{
+        paths,
+        settings::{detect_settings_changes, settings_changed_any, Settings, SettingsSection},
+        video::DetectedColorDepth,
+    },
+    input::routing::InputRoutingSet,
+    mini_salsa::theme::{Scheme, HIGH_CONTRAST, IMPERIAL, LIGHT, MONOCHROME},
+};
+
+const THEMES_FILE_NAME: &str = "themes.toml";
+
+/// Name of the scheme used when the settings don't pick a known one.
+pub const DEFAULT_SCHEME: &str = "Dark";
+
+/// Name of the scheme forced by the high contrast accessibility setting.
+pub const HIGH_CONTRAST_SCHEME: &str = "High contrast";
+
+/// The `[theme]` section of the [`Settings`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeSettings {
+    /// Name of the color scheme, see [`ThemeRegistry`].
+    pub name: String,
+}
+
+impl Default for ThemeSettings {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_SCHEME.to_string(),
+        }
+    }
+}
+
+/// The active color scheme. Widgets style themselves through it every frame,
+/// so changing it restyles the whole app on the next frame.
+#[derive(Debug, Clone, PartialEq, Resource, Deref)]
+pub struct Theme {
+    pub name: String,
+    #[deref]
+    pub scheme: Scheme,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            name: DEFAULT_SCHEME.to_string(),
+            scheme: IMPERIAL,
+        }
+    }
+}
+
+/// All schemes that can be selected, the built-in ones first.
+#[derive(Debug, Clone, Resource)]
+pub struct ThemeRegistry {
+    schemes: Vec<(String, Scheme)>,
+}
+
+impl Default for ThemeRegistry {
+    fn default() -> Self {
+        Self {
+            schemes: vec![
+                (DEFAULT_SCHEME.to_string(), IMPERIAL),
+                ("Light".to_string(), LIGHT),
+                (HIGH_CONTRAST_SCHEME.to_string(), HIGH_CONTRAST),
+                ("Monochrome".to_string(), MONOCHROME),
+            ],
+        }
+    }
+}
+
+impl ThemeRegistry {
+    pub fn get(&self, name: &str) -> Option<&Scheme> {
+        self.schemes
+            .iter()
+            .find(|(scheme_name, _)| scheme_name == name)
+            .map(|(_, scheme)| scheme)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.schemes.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    /// Adds a scheme, replacing any scheme with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, scheme: Scheme) {
+        let name = name.into();
+        match self.schemes.iter_mut().find(|(scheme_name, _)| *scheme_name == name) {
+            Some((_, existing)) => *existing = scheme,
+            None => self.schemes.push((name, scheme)),
+        }
+    }
+}
+
+/// Registers the built-in schemes and the custom ones from `themes.toml`, and keeps
+/// the [`Theme`] in sync with the scheme picked in the [`Settings`].
+pub struct ThemePlugin;
+
+impl Plugin for ThemePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ThemesConfigPath>()
+            .init_resource::<DetectedColorDepth>()
+            .init_resource::<Theme>()
+            .add_systems(PreStartup, load_themes)
+            .add_systems(
+                PreUpdate,
+                apply_theme
+                    .after(InputRoutingSet::Handle)
+                    .after(detect_settings_changes)
+                    .run_if(
+                        settings_changed_any(&[
+                            SettingsSection::Theme,
+                            SettingsSection::Video,
+                            SettingsSection::Accessibility,
+                        ])
+                        .or(resource_changed::<ThemeRegistry>)
+                        .or(resource_changed::<DetectedColorDepth>),
+                    ),
+            );
+    }
+}
+
+/// Location of the custom schemes file. `None` only offers the built-in schemes.
+#[derive(Debug, Clone, Resource)]
+pub struct ThemesConfigPath(pub Option<PathBuf>);
+
+impl Default for ThemesConfigPath {
+    fn default() -> Self {
+        Self(paths::config_file(THEMES_FILE_NAME))
+    }
+}
+
+/// Result of reading a schemes file.
+///
+/// Malformed schemes are skipped and described in `errors`.
+#[derive(Debug, Default)]
+pub struct LoadedSchemes {
+    pub schemes: Vec<(String, Scheme)>,
+    pub errors: Vec<String>,
+}
+
+/// A `[[scheme]]` table: a name and `palette = [base, top]` color pairs,
+/// which are spread over four shades like [`Scheme::linear4`] does.
+///
+/// Palettes left out are taken from the default scheme.
+#[derive(Debug, Deserialize)]
+struct SchemeEntry {
+    name: String,
+    #[serde(flatten)]
+    palettes: BTreeMap<String, [u32; 2]>,
+}
+
+impl SchemeEntry {
+    fn to_scheme(&self) -> Result<Scheme> {
+        let mut scheme = IMPERIAL;
+        for (palette, [base, top]) in &self.palettes {
+            *scheme.palette_mut(palette).ok_or_else(|| {
+                anyhow!(
+                    "unknown palette `{}`, expected one of {}",
+                    palette,
+                    Scheme::PALETTES.join(", ")
+                )
+            })? = Scheme::linear4(*base, *top);
+        }
+        Ok(scheme)
+    }
+}
+
+/// Parses the contents of a schemes file.
+pub fn parse_schemes(contents: &str) -> Result<LoadedSchemes> {
+    #[derive(Deserialize)]
+    struct RawFile {
+        #[serde(default)]
+        scheme: Vec<toml::Value>,
+    }
+
+    let raw: RawFile = toml::from_str(contents)?;
+    let mut loaded = LoadedSchemes::default();
+    for (index, value) in raw.scheme.into_iter().enumerate() {
+        let scheme = value
+            .try_into::<SchemeEntry>()
+            .map_err(anyhow::Error::from)
+            .and_then(|entry| Ok((entry.name.clone(), entry.to_scheme()?)));
+        match scheme {
+            Ok(scheme) => loaded.schemes.push(scheme),
+            Err(err) => {
+                let message = format!("scheme #{}: {:#}", index + 1, err);
+                loaded.errors.push(message.trim_end().to_string());
+            }
+        }
+    }
+    Ok(loaded)
+}
+
+fn load_themes(mut commands: Commands, config_path: Res<ThemesConfigPath>) {
+    let mut registry = ThemeRegistry::default();
+    if let Some(path) = config_path.0.as_deref().filter(|path| path.exists()) {
+        let loaded = fs::read_to_string(path)
+            .with_context(|| format!("reading {}", path.display()))
+            .and_then(|contents| {
+                parse_schemes(&contents).with_context(|| format!("parsing {}", path.display()))
+            });
+        match loaded {
+            Ok(loaded) => {
+                for err in &loaded.errors {
+                    warn!("Skipping malformed scheme in {}: {}", path.display(), err);
+                }
+                for (name, scheme) in loaded.schemes {
+                    registry.insert(name, scheme);
+                }
+            }
+            Err(err) => error!("Failed to load custom schemes: {:#}", err),
+        }
+    }
+    commands.insert_resource(registry);
+}
+
+fn apply_theme(
+    settings: Res<Settings>,
+    registry: Res<ThemeRegistry>,
+    detected: Res<DetectedColorDepth>,
+    mut theme: ResMut<Theme>,
+) {
+    let name = if settings.accessibility.high_contrast {
+        HIGH_CONTRAST_SCHEME
+    } else {
+        &settings.theme.name
+    };
+    let mut next = match registry.get(name) {
+        Some(scheme) => Theme {
+            name: name.to_string(),
+            scheme: scheme.clone(),
+        },
+        None => {
+            warn!("Unknown color scheme `{}`, using the default", name);
+            Theme::default()
+        }
+    };
+    next.scheme = next
+        .scheme
+        .downsampled(settings.video.effective_color_depth(&detected));
+    theme.set_if_neq(next);
+}
@@ -0,0 +1,23 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Reads a TOML file into `T`. A missing file yields `T::default()`.
+pub fn load<T: DeserializeOwned + Default>(path: &Path) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    toml::from_str(&contents).with_context(|| format!("parsing {}", path.display()))
+}
+
+/// Writes `value` as TOML, creating the parent directory if needed.
+pub fn save<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("creating {}", parent.display()))?;
+    }
+    let contents = toml::to_string_pretty(value)?;
+    fs::write(path, contents).with_context(|| format!("writing {}", path.display()))
+}
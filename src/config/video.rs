@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use crossterm::cursor::SetCursorStyle;
+use serde::{Deserialize, Serialize};
+
+use crate::mini_salsa::color_depth::ColorDepth;
+
+/// Frame rates offered on the Video screen.
+pub const FRAME_RATES: [u32; 4] = [30, 60, 120, 144];
+
+/// Characters used to draw borders.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BorderSet {
+    #[default]
+    Unicode,
+    Ascii,
+}
+
+impl BorderSet {
+    pub const ALL: [BorderSet; 2] = [BorderSet::Unicode, BorderSet::Ascii];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            BorderSet::Unicode => "Unicode",
+            BorderSet::Ascii => "ASCII",
+        }
+    }
+}
+
+/// Shape of the terminal cursor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CursorShape {
+    #[default]
+    Bar,
+    Block,
+    Underscore,
+}
+
+impl CursorShape {
+    pub const ALL: [CursorShape; 3] = [CursorShape::Bar, CursorShape::Block, CursorShape::Underscore];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CursorShape::Bar => "Bar",
+            CursorShape::Block => "Block",
+            CursorShape::Underscore => "Underscore",
+        }
+    }
+}
+
+/// The `[video]` section of the [`Settings`](crate::config::settings::Settings),
+/// applied live.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VideoSettings {
+    pub frame_rate: u32,
+    pub color_depth: ColorDepth,
+    pub border_set: BorderSet,
+    pub cursor_shape: CursorShape,
+    pub cursor_blink: bool,
+}
+
+impl Default for VideoSettings {
+    fn default() -> Self {
+        Self {
+            frame_rate: 60,
+            color_depth: ColorDepth::default(),
+            border_set: BorderSet::default(),
+            cursor_shape: CursorShape::default(),
+            cursor_blink: true,
+        }
+    }
+}
+
+impl VideoSettings {
+    /// Time budget of a single frame.
+    pub fn frame_time(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.frame_rate.max(1) as f64)
+    }
+
+    /// The color depth to draw with, after resolving [`ColorDepth::Auto`].
+    pub fn effective_color_depth(&self, detected: &DetectedColorDepth) -> ColorDepth {
+        self.color_depth.resolve(detected.0)
+    }
+
+    /// The cursor shape, blinking only if `blink` allows it as well.
+    pub fn cursor_style(&self, blink: bool) -> SetCursorStyle {
+        match (self.cursor_shape, self.cursor_blink && blink) {
+            (CursorShape::Bar, true) => SetCursorStyle::BlinkingBar,
+            (CursorShape::Bar, false) => SetCursorStyle::SteadyBar,
+            (CursorShape::Block, true) => SetCursorStyle::BlinkingBlock,
+            (CursorShape::Block, false) => SetCursorStyle::SteadyBlock,
+            (CursorShape::Underscore, true) => SetCursorStyle::BlinkingUnderScore,
+            (CursorShape::Underscore, false) => SetCursorStyle::SteadyUnderScore,
+        }
+    }
+
+    /// Replaces values that can't be shown on the Video screen with their defaults.
+    pub(crate) fn sanitized(mut self) -> Self {
+        if !FRAME_RATES.contains(&self.frame_rate) {
+            warn!("Unsupported frame rate {}, using the default", self.frame_rate);
+            self.frame_rate = VideoSettings::default().frame_rate;
+        }
+        self
+    }
+}
+
+/// What the terminal was detected to support, used when the color depth is
+/// [`ColorDepth::Auto`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource, Deref)]
+pub struct DetectedColorDepth(pub ColorDepth);
+
+impl Default for DetectedColorDepth {
+    fn default() -> Self {
+        Self(ColorDepth::detect())
+    }
+}
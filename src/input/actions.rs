@@ -0,0 +1,170 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
+
+use crate::{
+    config::settings::Settings,
+    input::routing::{InputCapture, InputEvent, InputQueue, InputRoutingSet},
+    widgets::key_bindings::{KeyBindingsTrait, KeyBindingsWidget, KeyChord, KeySequence},
+};
+
+/// A named action that screens react to instead of raw key codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameAction {
+    Quit,
+    OpenOptions,
+    Back,
+    Up,
+    Down,
+    Left,
+    Right,
+    Confirm,
+}
+
+/// The system key of every action.
+///
+/// A system key may trigger several actions, each screen only reacts to the ones it cares about.
+const SYSTEM_KEYS: &[(GameAction, KeyCode, KeyModifiers)] = &[
+    (GameAction::Quit, KeyCode::Char('q'), KeyModifiers::NONE),
+    (GameAction::OpenOptions, KeyCode::Esc, KeyModifiers::NONE),
+    (GameAction::Back, KeyCode::Esc, KeyModifiers::NONE),
+    (GameAction::Up, KeyCode::Up, KeyModifiers::NONE),
+    (GameAction::Down, KeyCode::Down, KeyModifiers::NONE),
+    (GameAction::Left, KeyCode::Left, KeyModifiers::NONE),
+    (GameAction::Right, KeyCode::Right, KeyModifiers::NONE),
+    (GameAction::Confirm, KeyCode::Enter, KeyModifiers::NONE),
+];
+
+impl GameAction {
+    /// All actions triggered by the given system key.
+    pub fn from_system_key(code: KeyCode, modifiers: KeyModifiers) -> impl Iterator<Item = Self> {
+        SYSTEM_KEYS
+            .iter()
+            .filter(move |(_, c, m)| *c == code && *m == modifiers)
+            .map(|(action, _, _)| *action)
+    }
+}
+
+/// Translates key presses into [`GameAction`]s using the [`KeyBindingsWidget`].
+///
+/// The actions are queued as [`InputEvent::Action`] right after the key that produced them,
+/// so they are routed to the active screen like any other input.
+pub struct ActionMappingPlugin;
+
+impl Plugin for ActionMappingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingSequence>()
+            .add_systems(PreUpdate, map_actions.in_set(InputRoutingSet::Global));
+    }
+}
+
+/// The chords of a key sequence such as `g g` typed so far.
+///
+/// Dropped when the next chord does not continue any bound sequence, or when
+/// it takes longer than the `sequence_timeout_ms` of the controls to come.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Resource)]
+pub struct PendingSequence {
+    sequence: Option<KeySequence>,
+    /// When the last chord was pressed.
+    pressed_at: Duration,
+}
+
+impl PendingSequence {
+    pub fn sequence(&self) -> Option<&KeySequence> {
+        self.sequence.as_ref()
+    }
+
+    fn clear(&mut self) {
+        self.sequence = None;
+    }
+}
+
+/// Resolves a pressed key to the system key it stands for.
+///
+/// Keys without a binding act as themselves, unless a binding moved their
+/// system key onto a different user key.
+pub fn resolve_system_key(
+    key_bindings: &KeyBindingsWidget,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+) -> Option<(KeyCode, KeyModifiers)> {
+    if let Ok(system_key) = key_bindings.convert_to_system_key_binding(code, modifiers) {
+        return Some(system_key);
+    }
+    let pressed = KeySequence::from((code, modifiers));
+    let remapped = key_bindings.key_bindings.values().any(|key_binding| {
+        key_binding.system_key == code
+            && key_binding.system_key_modifiers == modifiers
+            && key_binding.user_sequence() != pressed
+    });
+    if remapped {
+        None
+    } else {
+        Some((code, modifiers))
+    }
+}
+
+fn actions_for(system_key: KeyChord) -> impl Iterator<Item = InputEvent> {
+    GameAction::from_system_key(system_key.0, system_key.1).map(InputEvent::Action)
+}
+
+/// Queues the actions of each key press after it.
+///
+/// The chords of a bound sequence are taken from the queue: the first ones are
+/// kept in the [`PendingSequence`], the last one is replaced by the actions of
+/// the sequence. Screens reading raw keys set the [`InputCapture`], sequences
+/// are not matched then.
+pub fn map_actions(
+    mut queue: ResMut<InputQueue>,
+    key_bindings: Res<KeyBindingsWidget>,
+    mut pending: ResMut<PendingSequence>,
+    capture: Res<InputCapture>,
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+) {
+    let now = time.elapsed();
+    if capture.0 || now.saturating_sub(pending.pressed_at) > settings.controls.sequence_timeout() {
+        pending.clear();
+    }
+    queue.replace(|event| {
+        let InputEvent::Key(key_event) = &event else {
+            return vec![event];
+        };
+        let chord = (key_event.code, key_event.modifiers);
+        if key_event.kind == KeyEventKind::Release || matches!(chord.0, KeyCode::Modifier(_)) {
+            return vec![event];
+        }
+
+        if let Some(started) = pending.sequence.take() {
+            let sequence = started.then(chord);
+            if let Some(key_binding) = key_bindings.key_bindings.get(&sequence) {
+                return actions_for((key_binding.system_key, key_binding.system_key_modifiers))
+                    .collect();
+            }
+            if key_bindings.is_sequence_prefix(&sequence) {
+                *pending = PendingSequence {
+                    sequence: Some(sequence),
+                    pressed_at: now,
+                };
+                return Vec::new();
+            }
+            // not part of a sequence after all, the key counts on its own
+        }
+        let sequence = KeySequence::from(chord);
+        if !capture.0
+            && !key_bindings.key_bindings.contains_key(&sequence)
+            && key_bindings.is_sequence_prefix(&sequence)
+        {
+            *pending = PendingSequence {
+                sequence: Some(sequence),
+                pressed_at: now,
+            };
+            return Vec::new();
+        }
+        let system_key = resolve_system_key(&key_bindings, chord.0, chord.1);
+        std::iter::once(event)
+            .chain(system_key.into_iter().flat_map(actions_for))
+            .collect()
+    });
+}
@@ -0,0 +1,155 @@
+use bevy::{app::AppExit, prelude::*};
+use bevy_ratatui::event::{KeyEvent, MouseEvent};
+use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
+
+use crate::input::actions::GameAction;
+
+/// Order in which input flows through the app during `PreUpdate`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InputRoutingSet {
+    /// Terminal events are gathered into the [`InputQueue`].
+    Collect,
+    /// Global handlers see every event first and may consume it.
+    Global,
+    /// Remaining events are forwarded to the current [`Screen`](crate::states::screen::Screen).
+    Route,
+    /// Screens handle their own events.
+    Handle,
+}
+
+/// A terminal input event, as seen by the routing layer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputEvent {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    /// A key press translated through the key bindings.
+    Action(GameAction),
+}
+
+/// All input received this frame.
+///
+/// Global handlers running in [`InputRoutingSet::Global`] may remove events
+/// with [`InputQueue::consume`] so the screens never see them.
+#[derive(Debug, Default, Resource)]
+pub struct InputQueue {
+    events: Vec<InputEvent>,
+}
+
+impl InputQueue {
+    pub fn push(&mut self, event: InputEvent) {
+        self.events.push(event);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &InputEvent> {
+        self.events.iter()
+    }
+
+    /// Removes every event for which `consumed` returns true.
+    pub fn consume(&mut self, mut consumed: impl FnMut(&InputEvent) -> bool) {
+        self.events.retain(|event| !consumed(event));
+    }
+
+    /// Replaces every event with the events returned by `replace`, which may be none.
+    pub fn replace(&mut self, replace: impl FnMut(InputEvent) -> Vec<InputEvent>) {
+        let events = std::mem::take(&mut self.events);
+        self.events = events.into_iter().flat_map(replace).collect();
+    }
+
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
+
+/// Set while a screen reads raw keys, like the key bindings screen waiting for a new key.
+///
+/// Global handlers leave Esc and Back alone then, so they reach the screen.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub struct InputCapture(pub bool);
+
+/// Collects terminal input and routes it to the current [`Screen`](crate::states::screen::Screen).
+pub struct InputRoutingPlugin;
+
+impl Plugin for InputRoutingPlugin {
+    fn build(&self, app: &mut App) {
+        // Registered here as well so the routing also works without the crossterm event reader.
+        app.add_event::<KeyEvent>()
+            .add_event::<MouseEvent>()
+            .init_resource::<InputQueue>()
+            .init_resource::<InputCapture>()
+            .configure_sets(
+                PreUpdate,
+                (
+                    InputRoutingSet::Collect,
+                    InputRoutingSet::Global,
+                    InputRoutingSet::Route,
+                    InputRoutingSet::Handle,
+                )
+                    .chain()
+                    .after(bevy_ratatui::event::InputSet::EmitCrossterm),
+            )
+            .add_systems(PreUpdate, collect_input.in_set(InputRoutingSet::Collect))
+            .add_systems(PreUpdate, quit_handler.in_set(InputRoutingSet::Global));
+    }
+}
+
+/// Lets a screen plugin claim the input for a state, usually its [`Screen`](crate::states::screen::Screen).
+pub trait InputRoutingAppExt {
+    /// Registers the event `E` and sends it every unconsumed input event while `state` is active.
+    fn route_input<E>(&mut self, state: impl States) -> &mut Self
+    where
+        E: Event + From<InputEvent>;
+}
+
+impl InputRoutingAppExt for App {
+    fn route_input<E>(&mut self, state: impl States) -> &mut Self
+    where
+        E: Event + From<InputEvent>,
+    {
+        self.add_event::<E>().add_systems(
+            PreUpdate,
+            route_to_screen::<E>
+                .run_if(in_state(state))
+                .in_set(InputRoutingSet::Route),
+        )
+    }
+}
+
+fn collect_input(
+    mut queue: ResMut<InputQueue>,
+    mut keyboard_events: EventReader<KeyEvent>,
+    mut mouse_events: EventReader<MouseEvent>,
+) {
+    queue.clear();
+    for event in keyboard_events.read() {
+        queue.push(InputEvent::Key(event.clone()));
+    }
+    for event in mouse_events.read() {
+        queue.push(InputEvent::Mouse(*event));
+    }
+}
+
+/// Ctrl+C always quits, whichever screen is active.
+///
+/// Global handlers that take every event, like an open dialog, run after it.
+pub fn quit_handler(mut queue: ResMut<InputQueue>, mut app_exit: EventWriter<AppExit>) {
+    queue.consume(|event| match event {
+        InputEvent::Key(key_event) => {
+            let is_quit = key_event.code == KeyCode::Char('c')
+                && key_event.modifiers == KeyModifiers::CONTROL;
+            if is_quit && key_event.kind == KeyEventKind::Press {
+                app_exit.send_default();
+            }
+            is_quit
+        }
+        InputEvent::Mouse(_) | InputEvent::Action(_) => false,
+    });
+}
+
+fn route_to_screen<E>(queue: Res<InputQueue>, mut screen_events: EventWriter<E>)
+where
+    E: Event + From<InputEvent>,
+{
+    for event in queue.iter() {
+        screen_events.send(E::from(event.clone()));
+    }
+}
@@ -0,0 +1,100 @@
+pub mod widgets {
+    pub mod home;
+    pub mod options;
+    pub mod audio;
+    pub mod dialog;
+    pub mod game;
+    pub mod key_bindings;
+    pub mod menu;
+    pub mod pause;
+    pub mod pending_sequence;
+    pub mod settings_page;
+    pub mod splash;
+    pub mod video;
+}
+
+pub mod audio {
+    pub mod backend;
+}
+
+pub mod input {
+    pub mod actions;
+    pub mod routing;
+}
+
+pub mod config {
+    pub mod key_bindings;
+    pub mod audio;
+    pub mod paths;
+    pub mod settings;
+    pub mod theme;
+    pub mod toml_file;
+    pub mod video;
+}
+
+pub mod mini_salsa {
+    pub mod color_depth;
+    pub mod contrast;
+    pub mod theme;
+}
+
+pub mod states {
+    pub mod menu_screen;
+    pub mod app_state;
+    pub mod navigation;
+    pub mod screen;
+}
+
+pub mod terminal {
+    pub mod context;
+    pub mod error;
+    pub mod guard;
+}
+
+use audio::backend::AudioPlugin;
+use bevy::prelude::*;
+use config::{key_bindings::KeyBindingsConfigPlugin, settings::SettingsPlugin, theme::ThemePlugin};
+use input::{actions::ActionMappingPlugin, routing::InputRoutingPlugin};
+use states::{
+    app_state::{AppState, Gameplay},
+    menu_screen::MenuScreen,
+    navigation::NavigationPlugin,
+    screen::Screen,
+};
+use terminal::{context::FramePlugin, error::ExitReport};
+use widgets::{audio::AudioScreenPlugin, dialog::DialogPlugin, game::GameScreenPlugin, home::HomePlugin, key_bindings::KeyBindingsPlugin, options::OptionsPlugin, pause::PausePlugin, pending_sequence::PendingSequencePlugin, settings_page::SettingsPagePlugin, splash::SplashPlugin, video::VideoPlugin};
+
+/// Settings, input handling and every screen.
+///
+/// Needs the `StatesPlugin` and a [`terminal::context::TerminalContext`] resource to draw to,
+/// which is how the binary and the tests differ.
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_state::<AppState>()
+            .add_sub_state::<MenuScreen>()
+            .add_computed_state::<Gameplay>()
+            .add_computed_state::<Screen>()
+            .init_resource::<ExitReport>()
+            .add_plugins(FramePlugin)
+            .add_plugins(SettingsPlugin)
+            .add_plugins(KeyBindingsConfigPlugin)
+            .add_plugins(ThemePlugin)
+            .add_plugins(AudioPlugin::default())
+            .add_plugins(InputRoutingPlugin)
+            .add_plugins(NavigationPlugin)
+            .add_plugins(DialogPlugin)
+            .add_plugins(PendingSequencePlugin)
+            .add_plugins(SettingsPagePlugin)
+            .add_plugins(ActionMappingPlugin)
+            .add_plugins(SplashPlugin)
+            .add_plugins(HomePlugin)
+            .add_plugins(GameScreenPlugin)
+            .add_plugins(PausePlugin)
+            .add_plugins(OptionsPlugin)
+            .add_plugins(KeyBindingsPlugin)
+            .add_plugins(VideoPlugin)
+            .add_plugins(AudioScreenPlugin);
+    }
+}
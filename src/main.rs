@@ -1,101 +1,40 @@
-mod widgets {
-    pub mod home;
-    pub mod options;
-    pub mod key_bindings;
-}
+use std::time::Duration;
 
+use bevy_starter::{
+    terminal::{context::TerminalContext, error::ExitReport, guard::{install_hooks, TerminalGuard}},
+    GamePlugin,
+};
 
-mod mini_salsa {
-    pub mod theme;
-}
-
-mod states {
-    pub mod home_state;
-    pub mod app_state;
-    pub mod options_state;
-}
-use std::{io::stdout, time::Duration, error::Error};
-
-use states::app_state::AppState;
-use widgets::{home::{HomeEvent, HomePlugin}, options::{OptionsEvent, OptionsPlugin}};
-
-use crossterm::{cursor::{DisableBlinking, EnableBlinking, SetCursorStyle}, event::{DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, KeyCode, KeyEventKind, MouseEventKind}, terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen}, ExecutableCommand};
 use bevy::{
-    app::{AppExit, ScheduleRunnerPlugin},
+    app::ScheduleRunnerPlugin,
     prelude::*, 
     state::app::StatesPlugin,
 };
-use bevy_ratatui::{
-    error::exit_on_error, event::{KeyEvent, MouseEvent}, terminal::RatatuiContext, RatatuiPlugins,
-};
+use bevy_ratatui::{error::ErrorPlugin, terminal::TerminalPlugin, RatatuiPlugins};
 
-fn main() -> Result<(), Box<dyn Error>> {    
-    let frame_rate = Duration::from_secs_f64(1.0/60.0);
-    stdout().execute(EnterAlternateScreen)?;
-    stdout().execute(EnableMouseCapture)?;
-    stdout().execute(EnableBlinking)?;
-    stdout().execute(SetCursorStyle::BlinkingBar)?;
-    stdout().execute(EnableBracketedPaste)?;
-    enable_raw_mode()?;
-    App::new()
+fn main() -> color_eyre::Result<()> {    
+    install_hooks()?;
+    let guard = TerminalGuard::enter()?;
+    let mut app = App::new();
+    app
         .add_plugins(bevy::log::LogPlugin::default())
+        // the terminal is set up by the guard and drawn to through the TerminalContext,
+        // panics and errors are reported by our own hooks
         .add_plugins(RatatuiPlugins{
             enable_mouse_capture: true,
             ..default()
-        })        
-        .add_plugins(MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(frame_rate)))
+        }.build().disable::<TerminalPlugin>().disable::<ErrorPlugin>())        
+        .insert_resource(TerminalContext::crossterm()?)
+        // the frame rate is limited by the video settings
+        .add_plugins(MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(Duration::ZERO)))
         .add_plugins(StatesPlugin)        
-        .init_state::<AppState>()
-        .add_plugins(HomePlugin)    
-        .add_plugins(OptionsPlugin)    
-        .add_systems(PreUpdate, keyboard_events_handler)
-        .add_systems(PreUpdate, mouse_events_handler)
+        .add_plugins(GamePlugin)
         .run();
 
-        disable_raw_mode()?;
-        stdout().execute(DisableBracketedPaste)?;
-        stdout().execute(SetCursorStyle::DefaultUserShape)?;
-        stdout().execute(DisableBlinking)?;
-        stdout().execute(DisableMouseCapture)?;
-        stdout().execute(LeaveAlternateScreen)?;
-        Ok(())
-}
-
-fn mouse_events_handler(
-    app_state: ResMut<State<AppState>>,
-    mut mouse_events: EventReader<MouseEvent>,
-    mut home_events: EventWriter<HomeEvent>,
-    mut options_events: EventWriter<OptionsEvent>,
-) {
-    let app_state = app_state.get();
-    for event in mouse_events.read() {
-        match app_state {
-            AppState::Home => {
-                home_events.send(HomeEvent::MouseEvent(event.clone()));
-            }
-            AppState::Options => {
-                options_events.send(OptionsEvent::MouseEvent(event.clone()));
-            }
-        }
-    }
-}
-
-fn keyboard_events_handler(
-    app_state: Res<State<AppState>>,
-    mut keyboard_events: EventReader<KeyEvent>,   
-    mut home_events: EventWriter<HomeEvent>,  
-    mut options_events: EventWriter<OptionsEvent>  
-) {
-    let app_state = app_state.get();
-    // get event
-    for event in keyboard_events.read() {
-        match app_state {
-            AppState::Home => {
-                home_events.send(HomeEvent::KeyEvent(event.clone()));
-            }
-            AppState::Options => {
-                options_events.send(OptionsEvent::KeyEvent(event.clone()));
-            }
-        }
+    // leave the alternate screen before anything is printed
+    drop(guard);
+    match app.world_mut().remove_resource::<ExitReport>() {
+        Some(ExitReport(Some(report))) => Err(report),
+        _ => Ok(()),
     }
 }
\ No newline at end of file
@@ -0,0 +1,169 @@
+use std::env;
+
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// How many colors the terminal can show.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorDepth {
+    /// Whatever [`ColorDepth::detect`] finds.
+    #[default]
+    Auto,
+    /// 24 bit RGB colors.
+    TrueColor,
+    /// The xterm 256 color palette.
+    Ansi256,
+    /// The 16 named ANSI colors.
+    Ansi16,
+}
+
+impl ColorDepth {
+    pub const ALL: [ColorDepth; 4] = [
+        ColorDepth::Auto,
+        ColorDepth::TrueColor,
+        ColorDepth::Ansi256,
+        ColorDepth::Ansi16,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColorDepth::Auto => "Auto",
+            ColorDepth::TrueColor => "True color",
+            ColorDepth::Ansi256 => "256 colors",
+            ColorDepth::Ansi16 => "16 colors",
+        }
+    }
+
+    /// Guesses what the terminal supports from the environment.
+    pub fn detect() -> ColorDepth {
+        // Windows Terminal supports true color but sets neither variable.
+        if env::var_os("WT_SESSION").is_some() {
+            return ColorDepth::TrueColor;
+        }
+        Self::detect_from(
+            env::var("COLORTERM").ok().as_deref(),
+            env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    /// Guesses the color depth from the values of `COLORTERM` and `TERM`.
+    ///
+    /// Unknown terminals get the 16 colors every terminal has.
+    pub fn detect_from(colorterm: Option<&str>, term: Option<&str>) -> ColorDepth {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return ColorDepth::TrueColor;
+        }
+        match term.unwrap_or_default() {
+            term if term.ends_with("-direct") || term.contains("truecolor") => {
+                ColorDepth::TrueColor
+            }
+            term if term.contains("256color") => ColorDepth::Ansi256,
+            _ => ColorDepth::Ansi16,
+        }
+    }
+
+    /// The depth to draw with, `detected` stands in for [`ColorDepth::Auto`].
+    pub fn resolve(&self, detected: ColorDepth) -> ColorDepth {
+        match (self, detected) {
+            (ColorDepth::Auto, ColorDepth::Auto) => ColorDepth::TrueColor,
+            (ColorDepth::Auto, detected) => detected,
+            (depth, _) => *depth,
+        }
+    }
+
+    /// Maps a color to the nearest one this depth can show.
+    ///
+    /// [`ColorDepth::Auto`] has to be resolved first and leaves colors as they are.
+    pub fn convert(&self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::Auto | ColorDepth::TrueColor, color) => color,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => nearest_indexed(r, g, b),
+            (ColorDepth::Ansi256, color) => color,
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_ansi16(r, g, b),
+            (ColorDepth::Ansi16, Color::Indexed(n)) if n >= 16 => {
+                let (r, g, b) = indexed_rgb(n);
+                nearest_ansi16(r, g, b)
+            }
+            (ColorDepth::Ansi16, Color::Indexed(n)) => ANSI16[n as usize].0,
+            (ColorDepth::Ansi16, color) => color,
+        }
+    }
+}
+
+/// The 16 named colors with their VGA values.
+pub const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (170, 0, 0)),
+    (Color::Green, (0, 170, 0)),
+    (Color::Yellow, (170, 85, 0)),
+    (Color::Blue, (0, 0, 170)),
+    (Color::Magenta, (170, 0, 170)),
+    (Color::Cyan, (0, 170, 170)),
+    (Color::Gray, (170, 170, 170)),
+    (Color::DarkGray, (85, 85, 85)),
+    (Color::LightRed, (255, 85, 85)),
+    (Color::LightGreen, (85, 255, 85)),
+    (Color::LightYellow, (255, 255, 85)),
+    (Color::LightBlue, (85, 85, 255)),
+    (Color::LightMagenta, (255, 85, 255)),
+    (Color::LightCyan, (85, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Channel values of the 6x6x6 color cube in the 256 color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance((r0, g0, b0): (u8, u8, u8), (r1, g1, b1): (u8, u8, u8)) -> u32 {
+    let dr = r0 as i32 - r1 as i32;
+    let dg = g0 as i32 - g1 as i32;
+    let db = b0 as i32 - b1 as i32;
+    (dr * dr + dg * dg + db * db) as u32
+}
+
+/// RGB value of an entry in the 256 color palette.
+pub fn indexed_rgb(n: u8) -> (u8, u8, u8) {
+    match n {
+        0..=15 => ANSI16[n as usize].1,
+        16..=231 => {
+            let v = n - 16;
+            (
+                CUBE_LEVELS[(v / 36) as usize],
+                CUBE_LEVELS[((v / 6) % 6) as usize],
+                CUBE_LEVELS[(v % 6) as usize],
+            )
+        }
+        232..=255 => {
+            let level = 8 + 10 * (n - 232);
+            (level, level, level)
+        }
+    }
+}
+
+/// Nearest entry of the color cube or the grayscale ramp.
+pub fn nearest_indexed(r: u8, g: u8, b: u8) -> Color {
+    let nearest_level = |c: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|i| (CUBE_LEVELS[*i] as i32 - c as i32).abs())
+            .unwrap_or(0) as u8
+    };
+    let cube = 16 + 36 * nearest_level(r) + 6 * nearest_level(g) + nearest_level(b);
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray = 232 + ((average.saturating_sub(8) + 5) / 10).min(23) as u8;
+
+    if distance((r, g, b), indexed_rgb(gray)) < distance((r, g, b), indexed_rgb(cube)) {
+        Color::Indexed(gray)
+    } else {
+        Color::Indexed(cube)
+    }
+}
+
+/// Nearest of the 16 named colors.
+pub fn nearest_ansi16(r: u8, g: u8, b: u8) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, rgb)| distance((r, g, b), *rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
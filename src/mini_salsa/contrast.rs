@@ -0,0 +1,121 @@
+use std::fmt;
+
+use ratatui::style::{Color, Style};
+
+use crate::mini_salsa::{
+    color_depth::{indexed_rgb, ANSI16},
+    theme::Scheme,
+};
+
+/// WCAG AA minimum for normal text.
+pub const MIN_TEXT_CONTRAST: f64 = 4.5;
+
+/// WCAG AA minimum for large text and user interface components.
+pub const MIN_LARGE_TEXT_CONTRAST: f64 = 3.0;
+
+/// RGB value of a color, named and indexed colors use their VGA and xterm values.
+///
+/// `Reset` depends on the terminal and has none.
+pub fn rgb(color: Color) -> Option<(u8, u8, u8)> {
+    match color {
+        Color::Reset => None,
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(n) => Some(indexed_rgb(n)),
+        named => ANSI16
+            .iter()
+            .find(|(color, _)| *color == named)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+/// Relative luminance as defined by WCAG 2, from 0 for black to 1 for white.
+pub fn relative_luminance(color: Color) -> Option<f64> {
+    fn linear(channel: u8) -> f64 {
+        let c = channel as f64 / 255.0;
+        if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        }
+    }
+    let (r, g, b) = rgb(color)?;
+    Some(0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b))
+}
+
+/// WCAG contrast ratio between two colors, from 1 to 21.
+pub fn contrast_ratio(fg: Color, bg: Color) -> Option<f64> {
+    let fg = relative_luminance(fg)?;
+    let bg = relative_luminance(bg)?;
+    let (lighter, darker) = if fg > bg { (fg, bg) } else { (bg, fg) };
+    Some((lighter + 0.05) / (darker + 0.05))
+}
+
+/// A style of a scheme whose text doesn't stand out enough from its background.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContrastIssue {
+    pub style: &'static str,
+    pub fg: Color,
+    pub bg: Color,
+    pub ratio: f64,
+    pub minimum: f64,
+}
+
+impl fmt::Display for ContrastIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} on {} has a contrast of {:.2}, needs {:.1}",
+            self.style, self.fg, self.bg, self.ratio, self.minimum
+        )
+    }
+}
+
+/// Every style of the scheme that text is drawn with.
+///
+/// Borders, scrollbars and disabled items aren't text and are left out.
+pub fn text_styles(scheme: &Scheme) -> Vec<(&'static str, Style)> {
+    let statusline = scheme.statusline_style();
+    let table = scheme.table_style();
+    vec![
+        ("focus", scheme.focus()),
+        ("select", scheme.select()),
+        ("text_input", scheme.text_input()),
+        ("text_input_focus", scheme.text_input_focus()),
+        ("block_title", scheme.block_title()),
+        ("table", scheme.table()),
+        ("table_header", scheme.table_header()),
+        ("table_footer", scheme.table_footer()),
+        ("table_style.header", table.header.unwrap_or_default()),
+        ("text_focus", scheme.text_focus()),
+        ("text_select", scheme.text_select()),
+        ("data", scheme.data()),
+        ("dialog_style", scheme.dialog_style()),
+        ("status_style", scheme.status_style()),
+        ("statusline_style[1]", statusline[1]),
+        ("statusline_style[2]", statusline[2]),
+        ("statusline_style[3]", statusline[3]),
+        ("button_style", scheme.button_style()),
+        ("armed_style", scheme.armed_style()),
+        ("hover_style", scheme.hover_style()),
+    ]
+}
+
+/// Checks every text style of the scheme against `minimum`, see [`MIN_TEXT_CONTRAST`].
+///
+/// Styles with a `Reset` or missing color can't be checked and are skipped.
+pub fn check_contrast(scheme: &Scheme, minimum: f64) -> Vec<ContrastIssue> {
+    text_styles(scheme)
+        .into_iter()
+        .filter_map(|(style, colors)| {
+            let (fg, bg) = (colors.fg?, colors.bg?);
+            let ratio = contrast_ratio(fg, bg)?;
+            (ratio < minimum).then_some(ContrastIssue {
+                style,
+                fg,
+                bg,
+                ratio,
+                minimum,
+            })
+        })
+        .collect()
+}
@@ -2,7 +2,17 @@ use rat_ftable::TableStyle;
 use rat_scrolled::ScrollStyle;
 use ratatui::style::{Color, Style, Stylize};
 
-#[derive(Debug, Default, Clone)]
+use crate::mini_salsa::{
+    color_depth::ColorDepth,
+    contrast::{contrast_ratio, relative_luminance},
+};
+
+/// A color scheme. Every palette holds four shades from a base to a top color,
+/// see [`Scheme::linear4`].
+///
+/// `white` are the text shades and `black` the background shades. In a light scheme
+/// `white` is dark and `black` is light, [`Scheme::text_color`] takes care of that.
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Scheme {
     pub white: [Color; 4],
     pub black: [Color; 4],
@@ -128,6 +138,37 @@ impl Scheme {
         self.style(self.secondary[n])
     }
 
+    /// Names of the palettes, as used in scheme files.
+    pub const PALETTES: [&'static str; 17] = [
+        "white", "black", "gray", "red", "orange", "yellow", "limegreen", "green", "bluegreen",
+        "cyan", "blue", "deepblue", "purple", "magenta", "redpink", "primary", "secondary",
+    ];
+
+    /// The palette with the given name, see [`Scheme::PALETTES`].
+    pub fn palette_mut(&mut self, name: &str) -> Option<&mut [Color; 4]> {
+        let palette = match name {
+            "white" => &mut self.white,
+            "black" => &mut self.black,
+            "gray" => &mut self.gray,
+            "red" => &mut self.red,
+            "orange" => &mut self.orange,
+            "yellow" => &mut self.yellow,
+            "limegreen" => &mut self.limegreen,
+            "green" => &mut self.green,
+            "bluegreen" => &mut self.bluegreen,
+            "cyan" => &mut self.cyan,
+            "blue" => &mut self.blue,
+            "deepblue" => &mut self.deepblue,
+            "purple" => &mut self.purple,
+            "magenta" => &mut self.magenta,
+            "redpink" => &mut self.redpink,
+            "primary" => &mut self.primary,
+            "secondary" => &mut self.secondary,
+            _ => return None,
+        };
+        Some(palette)
+    }
+
     /// Focus style
     pub fn focus(&self) -> Style {
         let bg = self.primary[2];
@@ -168,11 +209,11 @@ impl Scheme {
     }
 
     pub fn table_header(&self) -> Style {
-        Style::default().fg(self.white[1]).bg(self.blue[2])
+        self.style(self.blue[2])
     }
 
     pub fn table_footer(&self) -> Style {
-        Style::default().fg(self.white[1]).bg(self.blue[2])
+        self.style(self.blue[2])
     }
 
     /// Focused text field style.
@@ -228,7 +269,17 @@ impl Scheme {
     }
 
     pub fn armed_style(&self) -> Style {
-        Style::default().fg(self.black[0]).bg(self.secondary[0])
+        self.style(self.secondary[0])
+    }
+
+    /// Button under the mouse.
+    pub fn hover_style(&self) -> Style {
+        self.style(self.primary[1])
+    }
+
+    /// Button that can't be used right now.
+    pub fn disabled_style(&self) -> Style {
+        Style::default().fg(self.gray[1]).bg(self.black[1])
     }
 
     /// Complete ScrolledStyle
@@ -251,15 +302,9 @@ impl Scheme {
     pub fn statusline_style(&self) -> Vec<Style> {
         vec![
             self.status_style(),
-            Style::default()
-                .fg(self.text_color(self.white[0]))
-                .bg(self.blue[3]),
-            Style::default()
-                .fg(self.text_color(self.white[0]))
-                .bg(self.blue[2]),
-            Style::default()
-                .fg(self.text_color(self.white[0]))
-                .bg(self.blue[1]),
+            self.style(self.blue[3]),
+            self.style(self.blue[2]),
+            self.style(self.blue[1]),
         ]
     }
 
@@ -310,68 +355,41 @@ impl Scheme {
         ]
     }
 
-    /// This gives back `white[3]` or `black[0]` for text foreground
-    /// providing good contrast to the given background.
+    /// This gives back `white[3]` or `black[0]` for text foreground,
+    /// whichever has the higher contrast ratio to the given background.
+    /// See [`contrast`](crate::mini_salsa::contrast) for how that is computed.
     ///
-    /// This converts RGB to grayscale and takes the grayscale value
-    /// of VGA cyan as threshold, which is about 105 out of 255.
-    /// This point is a bit arbitrary, just based on what I
-    /// perceive as acceptable. But it produces a good reading
-    /// contrast in my experience.
-    ///
-    /// For the named colors it takes the VGA equivalent as a base.
-    /// For indexed colors it splits the range in half as an estimate.
+    /// On `Reset` the lighter of the two is used, most terminals default
+    /// to a dark background.
     pub fn text_color(&self, color: Color) -> Color {
-        match color {
-            Color::Reset => self.white[3],
-            Color::Black => self.white[3],        //0
-            Color::Red => self.white[3],          //1
-            Color::Green => self.white[3],        //2
-            Color::Yellow => self.white[3],       //3
-            Color::Blue => self.white[3],         //4
-            Color::Magenta => self.white[3],      //5
-            Color::Cyan => self.white[3],         //6
-            Color::Gray => self.black[0],         //7
-            Color::DarkGray => self.white[3],     //8
-            Color::LightRed => self.black[0],     //9
-            Color::LightGreen => self.black[0],   //10
-            Color::LightYellow => self.black[0],  //11
-            Color::LightBlue => self.white[3],    //12
-            Color::LightMagenta => self.black[0], //13
-            Color::LightCyan => self.black[0],    //14
-            Color::White => self.black[0],        //15
-            Color::Rgb(r, g, b) => {
-                // The formula used in the GIMP is Y = 0.3R + 0.59G + 0.11B;
-                let grey = r as f32 * 0.3f32 + g as f32 * 0.59f32 + b as f32 * 0.11f32;
-                if grey >= 105f32 {
-                    self.black[0]
-                } else {
-                    self.white[3]
-                }
+        let (light, dark) = if self.is_light() {
+            (self.black[0], self.white[3])
+        } else {
+            (self.white[3], self.black[0])
+        };
+        match (contrast_ratio(light, color), contrast_ratio(dark, color)) {
+            (Some(light_ratio), Some(dark_ratio)) if dark_ratio > light_ratio => dark,
+            _ => light,
+        }
+    }
+
+    /// Whether the text shades are darker than the background shades.
+    pub fn is_light(&self) -> bool {
+        relative_luminance(self.black[0]) > relative_luminance(self.white[3])
+    }
+
+    /// The scheme with every color mapped to the nearest one `depth` can show.
+    ///
+    /// Text colors are picked from the mapped colors, so the contrast holds
+    /// on terminals with fewer colors.
+    pub fn downsampled(&self, depth: ColorDepth) -> Scheme {
+        let mut scheme = self.clone();
+        for name in Scheme::PALETTES {
+            if let Some(palette) = scheme.palette_mut(name) {
+                *palette = palette.map(|color| depth.convert(color));
             }
-            Color::Indexed(n) => match n {
-                0..=6 => self.white[3],
-                7 => self.black[0],
-                8 => self.white[3],
-                9..=11 => self.black[0],
-                12 => self.white[3],
-                13..=15 => self.black[0],
-                v @ 16..=231 => {
-                    if (v - 16) % 36 < 18 {
-                        self.white[3]
-                    } else {
-                        self.black[0]
-                    }
-                }
-                v @ 232..=255 => {
-                    if (v - 232) % 24 < 12 {
-                        self.white[3]
-                    } else {
-                        self.black[0]
-                    }
-                }
-            },
         }
+        scheme
     }
 }
 
@@ -380,9 +398,9 @@ impl Scheme {
 /// Uses purple and gold for primary/secondary.
 /// Other colors are bright, strong and slightly smudged.
 ///
-pub const THEME: Scheme = Scheme {
+pub const IMPERIAL: Scheme = Scheme {
     primary: Scheme::linear4(0x300057, 0x8c00fd),
-    secondary: Scheme::linear4(0x574b00, 0xffde00),
+    secondary: Scheme::linear4(0x665800, 0xffde00),
 
     white: Scheme::linear4(0xdedfe3, 0xf6f6f3),
     black: Scheme::linear4(0x0f1014, 0x2a2b37),
@@ -401,3 +419,78 @@ pub const THEME: Scheme = Scheme {
     magenta: Scheme::linear4(0x401640, 0xbd42bd),
     redpink: Scheme::linear4(0x47101d, 0xc33c5b),
 };
+
+/// Light scheme.
+///
+/// Dark text on paper-white backgrounds, with blue and amber for primary/secondary.
+pub const LIGHT: Scheme = Scheme {
+    primary: Scheme::linear4(0xc6d8f5, 0x2f6fc0),
+    secondary: Scheme::linear4(0x6b4a00, 0xd99a00),
+
+    white: Scheme::linear4(0x2a2b33, 0x101114),
+    black: Scheme::linear4(0xfafaf7, 0xd6d7dc),
+    gray: Scheme::linear4(0xb4b6c4, 0x6e7291),
+
+    red: Scheme::linear4(0xf2c4c4, 0xb01e1e),
+    orange: Scheme::linear4(0xf4d6b8, 0xb8620f),
+    yellow: Scheme::linear4(0xf7eeb0, 0x9c8200),
+    limegreen: Scheme::linear4(0xd8ecc0, 0x4f8a12),
+    green: Scheme::linear4(0xc4e8c4, 0x1e7d1e),
+    bluegreen: Scheme::linear4(0xc2e8dc, 0x1f7d60),
+    cyan: Scheme::linear4(0xc0ecec, 0x137a7a),
+    blue: Scheme::linear4(0xc6dcf2, 0x1c5fa8),
+    deepblue: Scheme::linear4(0xcacaf0, 0x2a2aa8),
+    purple: Scheme::linear4(0xdcc4f2, 0x6a14b8),
+    magenta: Scheme::linear4(0xeec4ee, 0x962b96),
+    redpink: Scheme::linear4(0xf2c6d0, 0xa82a48),
+};
+
+/// High contrast scheme.
+///
+/// Pure black backgrounds, white text and saturated signal colors.
+pub const HIGH_CONTRAST: Scheme = Scheme {
+    primary: Scheme::linear4(0x0000c0, 0x00ffff),
+    secondary: Scheme::linear4(0x806000, 0xffff00),
+
+    white: Scheme::linear4(0xffffff, 0xffffff),
+    black: Scheme::linear4(0x000000, 0x000000),
+    gray: Scheme::linear4(0x404040, 0xc0c0c0),
+
+    red: Scheme::linear4(0x800000, 0xff3030),
+    orange: Scheme::linear4(0x803000, 0xff8000),
+    yellow: Scheme::linear4(0x808000, 0xffff00),
+    limegreen: Scheme::linear4(0x408000, 0x80ff00),
+    green: Scheme::linear4(0x008000, 0x00ff00),
+    bluegreen: Scheme::linear4(0x008060, 0x00ffc0),
+    cyan: Scheme::linear4(0x008080, 0x00ffff),
+    blue: Scheme::linear4(0x0000a0, 0x4080ff),
+    deepblue: Scheme::linear4(0x000080, 0x0000ff),
+    purple: Scheme::linear4(0x400080, 0xa040ff),
+    magenta: Scheme::linear4(0x800080, 0xff00ff),
+    redpink: Scheme::linear4(0x800030, 0xff4080),
+};
+
+/// Monochrome scheme.
+///
+/// Only shades of gray, for terminals or eyes that don't do colors.
+pub const MONOCHROME: Scheme = Scheme {
+    primary: Scheme::linear4(0x3a3a3a, 0xbdbdbd),
+    secondary: Scheme::linear4(0x5a5a5a, 0xe0e0e0),
+
+    white: Scheme::linear4(0xd0d0d0, 0xf5f5f5),
+    black: Scheme::linear4(0x0c0c0c, 0x262626),
+    gray: Scheme::linear4(0x3c3c3c, 0x707070),
+
+    red: Scheme::linear4(0x404040, 0xb0b0b0),
+    orange: Scheme::linear4(0x404040, 0xb0b0b0),
+    yellow: Scheme::linear4(0x505050, 0xd0d0d0),
+    limegreen: Scheme::linear4(0x404040, 0xb0b0b0),
+    green: Scheme::linear4(0x404040, 0xb0b0b0),
+    bluegreen: Scheme::linear4(0x404040, 0xb0b0b0),
+    cyan: Scheme::linear4(0x404040, 0xb0b0b0),
+    blue: Scheme::linear4(0x303030, 0x909090),
+    deepblue: Scheme::linear4(0x303030, 0x909090),
+    purple: Scheme::linear4(0x303030, 0x909090),
+    magenta: Scheme::linear4(0x404040, 0xb0b0b0),
+    redpink: Scheme::linear4(0x404040, 0xb0b0b0),
+};
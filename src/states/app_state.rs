@@ -1,8 +1,25 @@
-use bevy::prelude::States;
+use bevy::prelude::{ComputedStates, States};
 
+/// Top-level flow of the app.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default, States)]
 pub enum AppState {
     #[default]
-    Home,
-    Options,
-}
\ No newline at end of file
+    Splash,
+    MainMenu,
+    InGame,
+    Paused,
+}
+
+/// Exists while a game is running, paused or not.
+///
+/// Whatever belongs to the current game lives from `OnEnter(Gameplay)` to `OnExit(Gameplay)`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Gameplay;
+
+impl ComputedStates for Gameplay {
+    type SourceStates = AppState;
+
+    fn compute(app_state: AppState) -> Option<Self> {
+        matches!(app_state, AppState::InGame | AppState::Paused).then_some(Gameplay)
+    }
+}
@@ -1,8 +0,0 @@
-use bevy::prelude::States;
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default, States)]
-pub enum HomeState {
-    #[default]
-    Home,
-    Options,
-}
\ No newline at end of file
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+use crate::states::app_state::AppState;
+
+/// The page of the menus that is open.
+///
+/// Only exists in the main menu and while paused, and starts over at `Main`
+/// whenever one of them is entered.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default, SubStates)]
+#[source(AppState = AppState::MainMenu | AppState::Paused)]
+pub enum MenuScreen {
+    /// The title screen in the main menu, the pause menu while paused.
+    #[default]
+    Main,
+    Options,
+    KeyBindings,
+    Video,
+    Audio,
+}
@@ -0,0 +1,131 @@
+use bevy::prelude::*;
+
+use crate::{
+    input::{
+        actions::{map_actions, GameAction},
+        routing::{InputCapture, InputEvent, InputQueue, InputRoutingSet},
+    },
+    states::menu_screen::MenuScreen,
+};
+
+/// A change to the [`ScreenStack`], applied at the end of input handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub enum Navigate {
+    /// Opens a page on top of the current one.
+    Push(MenuScreen),
+    /// Goes back to the page below, does nothing on the main page.
+    Pop,
+    /// Swaps the current page for another, so going back skips it.
+    Replace(MenuScreen),
+    /// Goes back to the main page.
+    Clear,
+}
+
+/// The menu pages opened on top of [`MenuScreen::Main`], most recent last.
+///
+/// The top of the stack is the [`MenuScreen`] state, so the usual `OnEnter` and
+/// `OnExit` schedules of [`Screen`](crate::states::screen::Screen) run whenever a
+/// page is shown or hidden. The stack is emptied when the menus are left.
+#[derive(Debug, Default, Clone, Resource)]
+pub struct ScreenStack {
+    pages: Vec<MenuScreen>,
+}
+
+impl ScreenStack {
+    /// The page that is shown.
+    pub fn current(&self) -> MenuScreen {
+        self.pages.last().copied().unwrap_or_default()
+    }
+
+    /// Pages opened on top of the main page.
+    pub fn pages(&self) -> &[MenuScreen] {
+        &self.pages
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.pages.is_empty()
+    }
+
+    pub fn apply(&mut self, navigate: Navigate) {
+        match navigate {
+            Navigate::Push(MenuScreen::Main) | Navigate::Clear => self.pages.clear(),
+            Navigate::Push(page) => self.pages.push(page),
+            Navigate::Pop => {
+                self.pages.pop();
+            }
+            Navigate::Replace(page) => {
+                self.pages.pop();
+                if page != MenuScreen::Main {
+                    self.pages.push(page);
+                }
+            }
+        }
+    }
+}
+
+/// Set while the current page has changes that would be lost by leaving it.
+///
+/// Back is then left to the page, so it can ask first.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub struct UnsavedChanges(pub bool);
+
+/// Keeps the [`MenuScreen`] state in line with the [`ScreenStack`] and goes back
+/// a page on [`GameAction::Back`].
+pub struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Navigate>()
+            .init_resource::<ScreenStack>()
+            .init_resource::<UnsavedChanges>()
+            .add_systems(
+                PreUpdate,
+                navigate_back
+                    .after(map_actions)
+                    .in_set(InputRoutingSet::Global),
+            )
+            .add_systems(PreUpdate, apply_navigation.after(InputRoutingSet::Handle));
+    }
+}
+
+/// Back leaves pushed pages, the main pages handle it themselves.
+pub fn navigate_back(
+    mut queue: ResMut<InputQueue>,
+    stack: Res<ScreenStack>,
+    capture: Res<InputCapture>,
+    unsaved: Res<UnsavedChanges>,
+    menu_screen: Option<Res<State<MenuScreen>>>,
+    mut navigate: EventWriter<Navigate>,
+) {
+    if menu_screen.is_none() || stack.is_root() || capture.0 || unsaved.0 {
+        return;
+    }
+    queue.consume(|event| {
+        let is_back = *event == InputEvent::Action(GameAction::Back);
+        if is_back {
+            navigate.send(Navigate::Pop);
+        }
+        is_back
+    });
+}
+
+pub fn apply_navigation(
+    mut events: EventReader<Navigate>,
+    mut stack: ResMut<ScreenStack>,
+    menu_screen: Option<Res<State<MenuScreen>>>,
+    mut next_menu_screen: ResMut<NextState<MenuScreen>>,
+) {
+    let Some(menu_screen) = menu_screen else {
+        events.clear();
+        if !stack.is_root() {
+            stack.apply(Navigate::Clear);
+        }
+        return;
+    };
+    for navigate in events.read() {
+        stack.apply(*navigate);
+    }
+    if *menu_screen.get() != stack.current() {
+        next_menu_screen.set(stack.current());
+    }
+}
@@ -0,0 +1,34 @@
+use bevy::prelude::ComputedStates;
+
+use crate::states::{app_state::AppState, menu_screen::MenuScreen};
+
+/// The screen that is drawn and receives input, computed from [`AppState`] and [`MenuScreen`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Screen {
+    Splash,
+    Home,
+    Game,
+    Pause,
+    Options,
+    KeyBindings,
+    Video,
+    Audio,
+}
+
+impl ComputedStates for Screen {
+    type SourceStates = (AppState, Option<MenuScreen>);
+
+    fn compute((app_state, menu_screen): (AppState, Option<MenuScreen>)) -> Option<Self> {
+        let screen = match (app_state, menu_screen) {
+            (AppState::Splash, _) => Screen::Splash,
+            (AppState::InGame, _) => Screen::Game,
+            (AppState::MainMenu, Some(MenuScreen::Main) | None) => Screen::Home,
+            (AppState::Paused, Some(MenuScreen::Main) | None) => Screen::Pause,
+            (_, Some(MenuScreen::Options)) => Screen::Options,
+            (_, Some(MenuScreen::KeyBindings)) => Screen::KeyBindings,
+            (_, Some(MenuScreen::Video)) => Screen::Video,
+            (_, Some(MenuScreen::Audio)) => Screen::Audio,
+        };
+        Some(screen)
+    }
+}
@@ -0,0 +1,152 @@
+use std::io::{self, stdout, Stdout};
+
+use bevy::prelude::*;
+use crossterm::{Command, ExecutableCommand};
+
+use crate::terminal::error::exit_on_error;
+use ratatui::{
+    backend::{Backend, CrosstermBackend, TestBackend},
+    buffer::Buffer,
+    Frame, Terminal,
+};
+
+/// Shows the frame drawn during an update once, with the overlays on top.
+///
+/// Screens draw in `Update`, overlays like dialogs in [`FrameSet::Overlay`].
+pub struct FramePlugin;
+
+impl Plugin for FramePlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(PostUpdate, (FrameSet::Overlay, FrameSet::Present).chain())
+            .add_systems(
+                PostUpdate,
+                present_frame.pipe(exit_on_error).in_set(FrameSet::Present),
+            );
+    }
+}
+
+/// Parts of a frame drawn in `PostUpdate`, after the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
+pub enum FrameSet {
+    /// Draws on top of the screen.
+    Overlay,
+    /// Puts the frame on the terminal.
+    Present,
+}
+
+/// The terminal the screens draw to.
+///
+/// The app draws to the real terminal through crossterm, tests draw to an in-memory
+/// [`TestBackend`] so the rendered [`Buffer`] can be inspected.
+#[derive(Resource)]
+pub struct TerminalContext {
+    output: Output,
+    /// Whether the frame of this update has been drawn to, see [`Self::draw`].
+    drawn: bool,
+}
+
+enum Output {
+    Crossterm(Terminal<CrosstermBackend<Stdout>>),
+    Test(Terminal<TestBackend>),
+}
+
+impl TerminalContext {
+    /// Draws to stdout. Raw mode and the alternate screen are left to the caller.
+    pub fn crossterm() -> io::Result<Self> {
+        let terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+        Ok(Self::new(Output::Crossterm(terminal)))
+    }
+
+    /// Draws to an in-memory buffer of the given size.
+    pub fn test(width: u16, height: u16) -> Self {
+        let terminal = Terminal::new(TestBackend::new(width, height))
+            .expect("the test backend never fails");
+        Self::new(Output::Test(terminal))
+    }
+
+    fn new(output: Output) -> Self {
+        Self {
+            output,
+            drawn: false,
+        }
+    }
+
+    /// Renders into the frame of this update, on top of what was drawn into it so far.
+    ///
+    /// Each frame starts from an empty buffer and is put on the terminal once,
+    /// by [`present_frame`].
+    pub fn draw(&mut self, render: impl FnOnce(&mut Frame)) -> io::Result<()> {
+        let first = !self.drawn;
+        self.drawn = true;
+        match &mut self.output {
+            Output::Crossterm(terminal) => draw(terminal, first, render),
+            Output::Test(terminal) => draw(terminal, first, render),
+        }
+    }
+
+    /// Puts the frame of this update on the terminal, if anything was drawn.
+    pub fn present(&mut self) -> io::Result<()> {
+        if !std::mem::take(&mut self.drawn) {
+            return Ok(());
+        }
+        match &mut self.output {
+            Output::Crossterm(terminal) => present(terminal),
+            Output::Test(terminal) => present(terminal),
+        }
+    }
+
+    /// Sends a crossterm command, e.g. a cursor style, to the real terminal.
+    ///
+    /// The test backend ignores commands.
+    pub fn execute(&mut self, command: impl Command) -> io::Result<()> {
+        match &mut self.output {
+            Output::Crossterm(terminal) => {
+                terminal.backend_mut().execute(command)?;
+            }
+            Output::Test(_) => {}
+        }
+        Ok(())
+    }
+
+    /// What the test backend currently shows, `None` on a real terminal.
+    pub fn test_buffer(&self) -> Option<&Buffer> {
+        match &self.output {
+            Output::Crossterm(_) => None,
+            Output::Test(terminal) => Some(terminal.backend().buffer()),
+        }
+    }
+
+    /// Resizes the test backend, a real terminal follows the window on its own.
+    pub fn resize_test(&mut self, width: u16, height: u16) {
+        if let Output::Test(terminal) = &mut self.output {
+            terminal.backend_mut().resize(width, height);
+        }
+    }
+}
+
+/// Shows what was drawn during this update, see [`FramePlugin`].
+pub fn present_frame(mut context: ResMut<TerminalContext>) -> color_eyre::Result<()> {
+    context.present()?;
+    Ok(())
+}
+
+/// [`Terminal::draw`], split so several systems can render into one frame.
+fn draw<B: Backend>(
+    terminal: &mut Terminal<B>,
+    first: bool,
+    render: impl FnOnce(&mut Frame),
+) -> io::Result<()> {
+    if first {
+        // follows the terminal size, only before drawing so a frame has one size
+        terminal.autoresize()?;
+    }
+    render(&mut terminal.get_frame());
+    Ok(())
+}
+
+fn present<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<()> {
+    terminal.flush()?;
+    terminal.hide_cursor()?;
+    terminal.swap_buffers();
+    terminal.backend_mut().flush()
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+use color_eyre::Report;
+
+/// The error that stopped the app, printed by `main` after the terminal is restored.
+#[derive(Debug, Default, Resource)]
+pub struct ExitReport(pub Option<Report>);
+
+/// Ends the app when a piped system fails, keeping the first error for [`ExitReport`].
+///
+/// Unlike logging it, this keeps the report from being drawn over on the alternate screen.
+pub fn exit_on_error(
+    In(result): In<color_eyre::Result<()>>,
+    mut report: ResMut<ExitReport>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    if let Err(err) = result {
+        error!("Error: {:?}", err);
+        report.0.get_or_insert(err);
+        app_exit.send(AppExit::error());
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    io::{self, stdout},
+    panic,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use color_eyre::config::HookBuilder;
+use crossterm::{
+    cursor::{DisableBlinking, SetCursorStyle, Show},
+    event::{
+        DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+        PopKeyboardEnhancementFlags,
+    },
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    ExecutableCommand,
+};
+
+/// Whether the terminal is currently in the app's mode, so it is restored only once.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Puts the terminal into the mode the app needs and restores it when dropped.
+///
+/// The panic hook installed by [`install_hooks`] restores it as well, so the terminal
+/// is usable again whether the app exits, returns an error or panics.
+pub struct TerminalGuard {
+    _private: (),
+}
+
+impl TerminalGuard {
+    /// Enters the alternate screen and enables raw mode, mouse capture and bracketed paste.
+    pub fn enter() -> io::Result<Self> {
+        ACTIVE.store(true, Ordering::SeqCst);
+        let guard = Self { _private: () };
+        stdout()
+            .execute(EnterAlternateScreen)?
+            .execute(EnableMouseCapture)?
+            .execute(EnableBracketedPaste)?;
+        enable_raw_mode()?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        if let Err(err) = restore() {
+            eprintln!("Failed to restore terminal: {}", err);
+        }
+    }
+}
+
+/// Undoes everything the app changed about the terminal. Does nothing if already restored.
+///
+/// Every step is attempted even if an earlier one fails, the first error is returned.
+pub fn restore() -> io::Result<()> {
+    if !ACTIVE.swap(false, Ordering::SeqCst) {
+        return Ok(());
+    }
+    let mut stdout = stdout();
+    [
+        disable_raw_mode(),
+        stdout.execute(PopKeyboardEnhancementFlags).map(|_| ()),
+        stdout.execute(DisableBracketedPaste).map(|_| ()),
+        stdout.execute(DisableMouseCapture).map(|_| ()),
+        stdout.execute(SetCursorStyle::DefaultUserShape).map(|_| ()),
+        stdout.execute(DisableBlinking).map(|_| ()),
+        stdout.execute(Show).map(|_| ()),
+        stdout.execute(LeaveAlternateScreen).map(|_| ()),
+    ]
+    .into_iter()
+    .collect()
+}
+
+/// Installs the `color_eyre` hooks, restoring the terminal before a panic report is printed
+/// so it ends up on the normal screen instead of the discarded alternate one.
+///
+/// Errors are reported by `main` once the [`TerminalGuard`] is dropped, see
+/// [`ExitReport`](super::error::ExitReport).
+pub fn install_hooks() -> color_eyre::Result<()> {
+    let (panic_hook, eyre_hook) = HookBuilder::default().into_hooks();
+    eyre_hook.install()?;
+
+    let panic_hook = panic_hook.into_panic_hook();
+    panic::set_hook(Box::new(move |panic_info| {
+        let _ = restore();
+        panic_hook(panic_info);
+    }));
+    Ok(())
+}
@@ -0,0 +1,364 @@
+use bevy::prelude::*;
+use bevy_ratatui::event::{KeyEvent, MouseEvent};
+use crossterm::event::{MouseButton, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Flex, Layout, Margin, Position, Rect},
+    text::Span,
+    widgets::{block, Block, Paragraph, StatefulWidgetRef, Widget, WidgetRef},
+};
+
+use crate::{
+    audio::backend::{Audio, AudioChannel, PlaySound},
+    config::{
+        audio::{AudioSettings, MAX_VOLUME},
+        settings::Settings,
+        theme::Theme,
+    },
+    input::{
+        actions::GameAction,
+        routing::{InputEvent, InputRoutingAppExt, InputRoutingSet},
+    },
+    mini_salsa::theme::Scheme,
+    states::screen::Screen,
+    terminal::{context::TerminalContext, error::exit_on_error},
+    widgets::{
+        settings_page::{SettingsCommand, SettingsDraft},
+        video::apply_video_settings,
+    },
+};
+
+/// Volume change per Left/Right press.
+const VOLUME_STEP: u8 = 5;
+/// Played on the SFX channel so the new volume can be heard.
+const PREVIEW_SOUND: &str = "ui_tick";
+
+pub struct AudioScreenPlugin;
+
+impl Plugin for AudioScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.route_input::<AudioEvent>(Screen::Audio)
+            .init_resource::<AudioScreenState>()
+            .add_systems(OnEnter(Screen::Audio), reset_audio_screen_state)
+            .add_systems(OnExit(Screen::Audio), restore_audio_settings)
+            .add_systems(PreUpdate, audio_events_handler.in_set(InputRoutingSet::Handle))
+            .add_systems(Update, render_audio.pipe(exit_on_error));
+    }
+}
+
+#[derive(Debug, Clone, Event, PartialEq, Eq)]
+pub enum AudioEvent {
+    MouseEvent(MouseEvent),
+    KeyEvent(KeyEvent),
+    Action(GameAction),
+}
+
+impl From<InputEvent> for AudioEvent {
+    fn from(event: InputEvent) -> Self {
+        match event {
+            InputEvent::Key(key_event) => AudioEvent::KeyEvent(key_event),
+            InputEvent::Mouse(mouse_event) => AudioEvent::MouseEvent(mouse_event),
+            InputEvent::Action(action) => AudioEvent::Action(action),
+        }
+    }
+}
+
+/// A row on the Audio screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioOption {
+    MasterVolume,
+    MasterMute,
+    MusicVolume,
+    MusicMute,
+    SfxVolume,
+    SfxMute,
+    Apply,
+    Reset,
+    Back,
+}
+
+impl AudioOption {
+    pub const ALL: [AudioOption; 9] = [
+        AudioOption::MasterVolume,
+        AudioOption::MasterMute,
+        AudioOption::MusicVolume,
+        AudioOption::MusicMute,
+        AudioOption::SfxVolume,
+        AudioOption::SfxMute,
+        AudioOption::Apply,
+        AudioOption::Reset,
+        AudioOption::Back,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AudioOption::MasterVolume => "Master volume",
+            AudioOption::MasterMute => "Mute all",
+            AudioOption::MusicVolume => "Music volume",
+            AudioOption::MusicMute => "Mute music",
+            AudioOption::SfxVolume => "Effects volume",
+            AudioOption::SfxMute => "Mute effects",
+            AudioOption::Apply => "Apply",
+            AudioOption::Reset => "Reset to defaults",
+            AudioOption::Back => "Back",
+        }
+    }
+
+    fn volume(&self, settings: &AudioSettings) -> Option<u8> {
+        match self {
+            AudioOption::MasterVolume => Some(settings.master_volume),
+            AudioOption::MusicVolume => Some(settings.music_volume),
+            AudioOption::SfxVolume => Some(settings.sfx_volume),
+            _ => None,
+        }
+    }
+
+    fn muted(&self, settings: &AudioSettings) -> Option<bool> {
+        match self {
+            AudioOption::MasterMute => Some(settings.master_muted),
+            AudioOption::MusicMute => Some(settings.music_muted),
+            AudioOption::SfxMute => Some(settings.sfx_muted),
+            _ => None,
+        }
+    }
+
+    fn volume_mut<'a>(&self, settings: &'a mut AudioSettings) -> Option<&'a mut u8> {
+        match self {
+            AudioOption::MasterVolume => Some(&mut settings.master_volume),
+            AudioOption::MusicVolume => Some(&mut settings.music_volume),
+            AudioOption::SfxVolume => Some(&mut settings.sfx_volume),
+            _ => None,
+        }
+    }
+
+    fn muted_mut<'a>(&self, settings: &'a mut AudioSettings) -> Option<&'a mut bool> {
+        match self {
+            AudioOption::MasterMute => Some(&mut settings.master_muted),
+            AudioOption::MusicMute => Some(&mut settings.music_muted),
+            AudioOption::SfxMute => Some(&mut settings.sfx_muted),
+            _ => None,
+        }
+    }
+
+    /// Raises or lowers a volume by one step, or toggles a mute switch.
+    pub fn step(&self, settings: &mut AudioSettings, up: bool) {
+        if let Some(volume) = self.volume_mut(settings) {
+            *volume = if up {
+                volume.saturating_add(VOLUME_STEP).min(MAX_VOLUME)
+            } else {
+                volume.saturating_sub(VOLUME_STEP)
+            };
+        } else if let Some(muted) = self.muted_mut(settings) {
+            *muted = !*muted;
+        }
+    }
+}
+
+/// Selection and hit areas of the Audio screen.
+#[derive(Debug, Default, Clone, Resource)]
+pub struct AudioScreenState {
+    pub selected: usize,
+    rows: Vec<(AudioOption, Rect)>,
+    sliders: Vec<(AudioOption, Rect)>,
+}
+
+impl AudioScreenState {
+    pub fn selected_option(&self) -> AudioOption {
+        AudioOption::ALL[self.selected.min(AudioOption::ALL.len() - 1)]
+    }
+
+    fn option_at(&self, x: u16, y: u16) -> Option<(usize, AudioOption)> {
+        self.rows
+            .iter()
+            .position(|(_, rect)| rect.contains(Position { x, y }))
+            .map(|index| (index, self.rows[index].0))
+    }
+
+    /// Volume for a click at column `x` on the slider of `option`.
+    ///
+    /// The leftmost cell is silence and the rightmost one full volume.
+    fn volume_at(&self, option: AudioOption, x: u16, y: u16) -> Option<u8> {
+        let (_, bar) = self
+            .sliders
+            .iter()
+            .find(|(o, rect)| *o == option && rect.contains(Position { x, y }))?;
+        let offset = (x - bar.x) as u32;
+        Some((offset * MAX_VOLUME as u32 / (bar.width as u32 - 1).max(1)) as u8)
+    }
+}
+
+pub struct AudioWidget<'a> {
+    pub settings: &'a AudioSettings,
+    pub theme: &'a Scheme,
+}
+
+impl StatefulWidgetRef for AudioWidget<'_> {
+    type State = AudioScreenState;
+
+    fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        Block::bordered()
+            .border_type(block::BorderType::Rounded)
+            .border_style(self.theme.block())
+            .title_style(self.theme.block_title())
+            .style(self.theme.data())
+            .title("Audio")
+            .render_ref(area, buf);
+
+        let [column] = Layout::horizontal([Constraint::Length(48)])
+            .flex(Flex::Center)
+            .areas(area.inner(Margin::new(2, 2)));
+        let rows = Layout::vertical(AudioOption::ALL.map(|_| Constraint::Length(2)))
+            .flex(Flex::Center)
+            .split(column);
+
+        state.rows.clear();
+        state.sliders.clear();
+        for (index, (option, row)) in AudioOption::ALL.iter().zip(rows.iter()).enumerate() {
+            let row = Rect { height: 1, ..*row };
+            let style = if index == state.selected {
+                self.theme.focus()
+            } else {
+                self.theme.data()
+            };
+            let [label_area, value_area] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(28)]).areas(row);
+            buf.set_style(row, style);
+            Span::from(format!(" {}", option.label())).render(label_area, buf);
+
+            if let Some(volume) = option.volume(self.settings) {
+                let [bar_area, percent_area] =
+                    Layout::horizontal([Constraint::Fill(1), Constraint::Length(5)])
+                        .areas(value_area);
+                let filled = (bar_area.width as u32 * volume as u32 / MAX_VOLUME as u32) as usize;
+                let empty = bar_area.width as usize - filled;
+                Span::from(format!("{}{}", "█".repeat(filled), "░".repeat(empty)))
+                    .render(bar_area, buf);
+                Paragraph::new(format!("{}%", volume))
+                    .alignment(Alignment::Right)
+                    .render(percent_area, buf);
+                state.sliders.push((*option, bar_area));
+            } else if let Some(muted) = option.muted(self.settings) {
+                Paragraph::new(if muted { "[x]" } else { "[ ]" })
+                    .alignment(Alignment::Right)
+                    .render(value_area, buf);
+            }
+            state.rows.push((*option, row));
+        }
+    }
+}
+
+fn reset_audio_screen_state(mut state: ResMut<AudioScreenState>) {
+    state.selected = 0;
+}
+
+/// Edits the [`SettingsDraft`]. The draft is played right away so the change
+/// can be heard, [`restore_audio_settings`] undoes that if it is not applied.
+fn audio_events_handler(
+    mut commands: EventWriter<SettingsCommand>,
+    mut events: EventReader<AudioEvent>,
+    mut state: ResMut<AudioScreenState>,
+    mut draft: ResMut<SettingsDraft>,
+    mut audio: ResMut<Audio>,
+    mut play_sound: EventWriter<PlaySound>,
+) {
+    let last = AudioOption::ALL.len() - 1;
+    for event in events.read() {
+        let mut next = draft.audio.clone();
+        let option = match event {
+            // only reaches the page while it has changes, see `UnsavedChanges`
+            AudioEvent::Action(GameAction::Back) => AudioOption::Back,
+            AudioEvent::Action(GameAction::Up) => {
+                state.selected = state.selected.saturating_sub(1);
+                continue;
+            }
+            AudioEvent::Action(GameAction::Down) => {
+                state.selected = (state.selected + 1).min(last);
+                continue;
+            }
+            AudioEvent::Action(GameAction::Left) => {
+                let option = state.selected_option();
+                option.step(&mut next, false);
+                option
+            }
+            AudioEvent::Action(GameAction::Right) => {
+                let option = state.selected_option();
+                option.step(&mut next, true);
+                option
+            }
+            AudioEvent::Action(GameAction::Confirm) => {
+                let option = state.selected_option();
+                if option.muted(&next).is_some() {
+                    option.step(&mut next, true);
+                }
+                option
+            }
+            AudioEvent::MouseEvent(mouse_event) => match mouse_event.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    let (x, y) = (mouse_event.column, mouse_event.row);
+                    let Some((index, option)) = state.option_at(x, y) else {
+                        continue;
+                    };
+                    state.selected = index;
+                    if let Some(volume) = state.volume_at(option, x, y) {
+                        if let Some(current) = option.volume_mut(&mut next) {
+                            *current = volume;
+                        }
+                    } else if option.muted(&next).is_some() {
+                        option.step(&mut next, true);
+                    }
+                    option
+                }
+                _ => continue,
+            },
+            AudioEvent::Action(_) | AudioEvent::KeyEvent(_) => continue,
+        };
+
+        match option {
+            AudioOption::Apply => {
+                commands.send(SettingsCommand::Apply);
+                continue;
+            }
+            AudioOption::Back => {
+                commands.send(SettingsCommand::Cancel);
+                continue;
+            }
+            AudioOption::Reset => next = AudioSettings::default(),
+            _ => {}
+        }
+        if draft.audio != next {
+            audio.apply_settings(&next);
+            draft.audio = next;
+            play_sound.send(PlaySound {
+                sound: PREVIEW_SOUND.to_string(),
+                channel: AudioChannel::Sfx,
+            });
+        }
+    }
+}
+
+fn restore_audio_settings(settings: Res<Settings>, mut audio: ResMut<Audio>) {
+    audio.apply_settings(&settings.audio);
+}
+
+fn render_audio(
+    screen: Res<State<Screen>>,
+    mut context: ResMut<TerminalContext>,
+    settings: Res<Settings>,
+    draft: Res<SettingsDraft>,
+    theme: Res<Theme>,
+    mut state: ResMut<AudioScreenState>,
+) -> color_eyre::Result<()> {
+    if screen.get() != &Screen::Audio {
+        return Ok(());
+    }
+    context.draw(|frame| {
+        let area = frame.area();
+        AudioWidget {
+            settings: &draft.audio,
+            theme: &theme,
+        }
+        .render_ref(area, frame.buffer_mut(), &mut state);
+        apply_video_settings(&settings.video, frame.buffer_mut());
+    })?;
+    Ok(())
+}
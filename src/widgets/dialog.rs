@@ -0,0 +1,228 @@
+use bevy::prelude::*;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    text::Text,
+    widgets::{block, Block, Clear, Paragraph, StatefulWidgetRef, Widget, WidgetRef},
+};
+
+use crate::{
+    config::{settings::Settings, theme::Theme},
+    input::{
+        actions::{map_actions, GameAction},
+        routing::{quit_handler, InputEvent, InputQueue, InputRoutingSet},
+    },
+    mini_salsa::theme::Scheme,
+    states::navigation::navigate_back,
+    terminal::{
+        context::{FrameSet, TerminalContext},
+        error::exit_on_error,
+    },
+    widgets::{
+        menu::{Menu, MenuState},
+        video::apply_video_settings,
+    },
+};
+
+/// Width of the dialog buttons.
+const BUTTON_WIDTH: u16 = 16;
+
+/// A question shown over the current screen until one of its buttons is chosen.
+#[derive(Debug, Clone)]
+pub struct Dialog {
+    /// Sent back in [`DialogClosed`], so the opener can tell its dialogs apart.
+    pub id: &'static str,
+    pub title: String,
+    pub message: String,
+    buttons: MenuState<usize>,
+}
+
+impl Dialog {
+    /// A dialog without buttons, add them with [`Dialog::button`].
+    pub fn new(id: &'static str, title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            id,
+            title: title.into(),
+            message: message.into(),
+            buttons: MenuState::new([]),
+        }
+    }
+
+    /// A Yes/No question with No focused, so confirming twice by accident is harmless.
+    pub fn confirm(id: &'static str, title: impl Into<String>, message: impl Into<String>) -> Self {
+        let mut dialog = Self::new(id, title, message).button("Yes").button("No");
+        dialog.buttons.focus(1);
+        dialog
+    }
+
+    /// Adds a button, its index is the [`DialogClosed::choice`] when it is chosen.
+    pub fn button(mut self, label: &str) -> Self {
+        let index = self.buttons.items.len();
+        self.buttons.items.extend(MenuState::new([(index, label)]).items);
+        self.buttons.reset();
+        self
+    }
+
+    pub fn buttons(&self) -> &MenuState<usize> {
+        &self.buttons
+    }
+}
+
+/// Sent when a dialog is closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct DialogClosed {
+    pub id: &'static str,
+    /// Index of the chosen button, `None` when the dialog was cancelled with Back.
+    pub choice: Option<usize>,
+}
+
+impl DialogClosed {
+    /// The first button was chosen, Yes for [`Dialog::confirm`].
+    pub fn is_confirmed(&self) -> bool {
+        self.choice == Some(0)
+    }
+}
+
+/// Open dialogs, the last one is on top and gets all input.
+#[derive(Debug, Default, Resource)]
+pub struct Dialogs {
+    open: Vec<Dialog>,
+}
+
+impl Dialogs {
+    pub fn open(&mut self, dialog: Dialog) {
+        self.open.push(dialog);
+    }
+
+    pub fn is_open(&self) -> bool {
+        !self.open.is_empty()
+    }
+
+    pub fn top(&self) -> Option<&Dialog> {
+        self.open.last()
+    }
+}
+
+/// Draws open dialogs over the current screen and keeps their input from reaching it.
+pub struct DialogPlugin;
+
+impl Plugin for DialogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DialogClosed>()
+            .init_resource::<Dialogs>()
+            .add_systems(
+                PreUpdate,
+                dialog_input_handler
+                    .after(quit_handler)
+                    .after(map_actions)
+                    .before(navigate_back)
+                    .in_set(InputRoutingSet::Global),
+            )
+            // into the frame the screen drew in `Update`
+            .add_systems(
+                PostUpdate,
+                render_dialog.pipe(exit_on_error).in_set(FrameSet::Overlay),
+            );
+    }
+}
+
+pub struct DialogWidget<'a> {
+    pub theme: &'a Scheme,
+}
+
+impl StatefulWidgetRef for DialogWidget<'_> {
+    type State = Dialog;
+
+    fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let message = Text::from(state.message.as_str());
+        let menu = Menu::new(self.theme).button_height(1).spacing(0);
+        let buttons_height = menu.height(state.buttons.items.len());
+        let width = (message.width() as u16)
+            .max(state.title.len() as u16)
+            .max(BUTTON_WIDTH)
+            + 4;
+        let height = message.height() as u16 + buttons_height + 3;
+
+        let [dialog_area] = Layout::vertical([Constraint::Length(height)])
+            .flex(Flex::Center)
+            .areas(area);
+        let [dialog_area] = Layout::horizontal([Constraint::Length(width)])
+            .flex(Flex::Center)
+            .areas(dialog_area);
+        Clear.render(dialog_area, buf);
+        let block = Block::bordered()
+            .border_type(block::BorderType::Rounded)
+            .border_style(self.theme.dialog_style())
+            .title_style(self.theme.dialog_style())
+            .style(self.theme.dialog_style())
+            .title(state.title.as_str());
+        let inner = block.inner(dialog_area);
+        block.render_ref(dialog_area, buf);
+
+        let [message_area, _, buttons_area] = Layout::vertical([
+            Constraint::Length(message.height() as u16),
+            Constraint::Length(1),
+            Constraint::Length(buttons_height),
+        ])
+        .areas(inner);
+        Paragraph::new(message)
+            .alignment(Alignment::Center)
+            .render(message_area, buf);
+        let [buttons_area] = Layout::horizontal([Constraint::Length(BUTTON_WIDTH)])
+            .flex(Flex::Center)
+            .areas(buttons_area);
+        menu.render_ref(buttons_area, buf, &mut state.buttons);
+    }
+}
+
+/// The top dialog takes every input event, Back cancels it.
+fn dialog_input_handler(
+    mut queue: ResMut<InputQueue>,
+    mut dialogs: ResMut<Dialogs>,
+    mut closed: EventWriter<DialogClosed>,
+) {
+    queue.consume(|event| {
+        let Some(dialog) = dialogs.open.last_mut() else {
+            return false;
+        };
+        let choice = match event {
+            InputEvent::Key(key_event) => dialog.buttons.handle_key(key_event).map(Some),
+            InputEvent::Mouse(mouse_event) => dialog.buttons.handle_mouse(mouse_event).map(Some),
+            InputEvent::Action(GameAction::Back) => Some(None),
+            InputEvent::Action(GameAction::Left) => {
+                dialog.buttons.focus_prev();
+                None
+            }
+            InputEvent::Action(GameAction::Right) => {
+                dialog.buttons.focus_next();
+                None
+            }
+            InputEvent::Action(action) => dialog.buttons.handle_action(*action).map(Some),
+        };
+        if let Some(choice) = choice {
+            closed.send(DialogClosed {
+                id: dialog.id,
+                choice,
+            });
+            dialogs.open.pop();
+        }
+        true
+    });
+}
+
+pub fn render_dialog(
+    mut context: ResMut<TerminalContext>,
+    mut dialogs: ResMut<Dialogs>,
+    settings: Res<Settings>,
+    theme: Res<Theme>,
+) -> color_eyre::Result<()> {
+    let Some(dialog) = dialogs.open.last_mut() else {
+        return Ok(());
+    };
+    context.draw(|frame| {
+        let area = frame.area();
+        frame.render_stateful_widget_ref(DialogWidget { theme: &theme }, area, dialog);
+        apply_video_settings(&settings.video, frame.buffer_mut());
+    })?;
+    Ok(())
+}
@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ratatui::event::{KeyEvent, MouseEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    text::Text,
+    widgets::{block, Block, Paragraph, Widget, WidgetRef},
+};
+
+use crate::{
+    config::{settings::Settings, theme::Theme},
+    input::{
+        actions::GameAction,
+        routing::{InputEvent, InputRoutingAppExt, InputRoutingSet},
+    },
+    mini_salsa::theme::Scheme,
+    states::{
+        app_state::{AppState, Gameplay},
+        screen::Screen,
+    },
+    terminal::{context::TerminalContext, error::exit_on_error},
+    widgets::video::apply_video_settings,
+};
+
+/// The running game. Exists from `OnEnter(Gameplay)` to `OnExit(Gameplay)`,
+/// so pausing keeps it and going back to the main menu drops it.
+#[derive(Debug, Default, Clone, Resource)]
+pub struct GameSession {
+    /// Time spent in game, without the time paused.
+    pub played: Duration,
+}
+
+pub struct GameScreenPlugin;
+
+impl Plugin for GameScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.route_input::<GameEvent>(Screen::Game)
+            .add_systems(OnEnter(Gameplay), start_session)
+            .add_systems(OnExit(Gameplay), end_session)
+            .add_systems(PreUpdate, game_events_handler.in_set(InputRoutingSet::Handle))
+            .add_systems(Update, advance_session.run_if(in_state(AppState::InGame)))
+            .add_systems(Update, render_game.pipe(exit_on_error));
+    }
+}
+
+#[derive(Debug, Clone, Event, PartialEq, Eq)]
+pub enum GameEvent {
+    MouseEvent(MouseEvent),
+    KeyEvent(KeyEvent),
+    Action(GameAction),
+}
+
+impl From<InputEvent> for GameEvent {
+    fn from(event: InputEvent) -> Self {
+        match event {
+            InputEvent::Key(key_event) => GameEvent::KeyEvent(key_event),
+            InputEvent::Mouse(mouse_event) => GameEvent::MouseEvent(mouse_event),
+            InputEvent::Action(action) => GameEvent::Action(action),
+        }
+    }
+}
+
+/// Stand-in for the game, shows how long it has been played.
+pub struct GameWidget<'a> {
+    pub theme: &'a Scheme,
+    pub session: &'a GameSession,
+}
+
+impl WidgetRef for GameWidget<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .border_type(block::BorderType::Rounded)
+            .border_style(self.theme.block())
+            .title_style(self.theme.block_title())
+            .style(self.theme.data())
+            .title("Game");
+        let inner = block.inner(area);
+        block.render_ref(area, buf);
+
+        let [text_area] = Layout::vertical([Constraint::Length(3)])
+            .flex(Flex::Center)
+            .areas(inner);
+        let text = Text::from(vec![
+            format!("Playing for {}s", self.session.played.as_secs()).into(),
+            "".into(),
+            "Esc pauses".into(),
+        ]);
+        Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .render(text_area, buf);
+    }
+}
+
+fn start_session(mut commands: Commands) {
+    commands.init_resource::<GameSession>();
+}
+
+fn end_session(mut commands: Commands) {
+    commands.remove_resource::<GameSession>();
+}
+
+fn advance_session(time: Res<Time>, session: Option<ResMut<GameSession>>) {
+    if let Some(mut session) = session {
+        session.played += time.delta();
+    }
+}
+
+fn game_events_handler(
+    mut app_state: ResMut<NextState<AppState>>,
+    mut events: EventReader<GameEvent>,
+) {
+    for event in events.read() {
+        if let GameEvent::Action(GameAction::Back) = event {
+            app_state.set(AppState::Paused);
+        }
+    }
+}
+
+fn render_game(
+    screen: Res<State<Screen>>,
+    mut context: ResMut<TerminalContext>,
+    settings: Res<Settings>,
+    session: Option<Res<GameSession>>,
+    theme: Res<Theme>,
+) -> color_eyre::Result<()> {
+    if screen.get() != &Screen::Game {
+        return Ok(());
+    }
+    let Some(session) = session else {
+        return Ok(());
+    };
+    context.draw(|frame| {
+        let area = frame.area();
+        let widget = GameWidget {
+            theme: &theme,
+            session: &session,
+        };
+        frame.render_widget_ref(widget, area);
+        apply_video_settings(&settings.video, frame.buffer_mut());
+    })?;
+    Ok(())
+}
@@ -1,30 +1,163 @@
 use bevy::prelude::*;
 
-use bevy_ratatui::{error::exit_on_error, event::{self, KeyEvent, MouseEvent}, terminal::RatatuiContext};
-use crossterm::event::KeyEventKind;
+use bevy_ratatui::event::{KeyEvent, MouseEvent};
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
-    style::{Color, Style},
-    widgets::{WidgetRef, Block, Borders},
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    text::Text,
+    widgets::{block, Block, Paragraph, StatefulWidgetRef, Widget, WidgetRef},
 };
 
-use crate::states::app_state::AppState;
+use crate::{
+    config::{settings::Settings, theme::Theme, video::BorderSet},
+    mini_salsa::theme::Scheme,
+    input::{
+        actions::GameAction,
+        routing::{InputEvent, InputRoutingAppExt, InputRoutingSet},
+    },
+    states::{
+        app_state::AppState,
+        menu_screen::MenuScreen,
+        navigation::Navigate,
+        screen::Screen,
+    },
+    terminal::{context::TerminalContext, error::exit_on_error},
+    widgets::{
+        menu::{Menu, MenuActivated, MenuState},
+        video::apply_video_settings,
+    },
+};
+
+const VERSION: &str = concat!(env!("CARGO_PKG_NAME"), " v", env!("CARGO_PKG_VERSION"));
+
+/// Title art of the Home screen, drawn with the variant that matches the
+/// [`BorderSet`] of the video settings.
+///
+/// Insert this resource before the [`HomePlugin`] to show your own title.
+/// Art that doesn't fit the terminal is replaced by `text`.
+#[derive(Debug, Clone, Resource)]
+pub struct TitleArt {
+    pub unicode: String,
+    pub ascii: String,
+    pub text: String,
+}
 
-pub struct HomeWidget;
+impl Default for TitleArt {
+    fn default() -> Self {
+        let unicode = [
+            "████  █████ █   █ █   █     ████ █████  ███  ████  █████ █████ ████",
+            "█   █ █     █   █  █ █     █       █   █   █ █   █   █   █     █   █",
+            "████  ████  █   █   █       ███    █   █████ ████    █   ████  ████",
+            "█   █ █      █ █    █          █   █   █   █ █  █    █   █     █  █",
+            "████  █████   █     █      ████    █   █   █ █   █   █   █████ █   █",
+        ]
+        .join("\n");
+        Self {
+            ascii: unicode.replace('█', "#"),
+            unicode,
+            text: "BEVY STARTER".to_string(),
+        }
+    }
+}
+
+impl TitleArt {
+    pub fn for_border_set(&self, border_set: BorderSet) -> &str {
+        match border_set {
+            BorderSet::Unicode => &self.unicode,
+            BorderSet::Ascii => &self.ascii,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum HomeComponents {
+    Continue,
+    NewGame,
+    Options,
+    Quit,
+}
+
+/// The items of the main menu, nothing hovered or focused yet.
+///
+/// Continue stays disabled until there are saved games to continue.
+pub fn home_menu() -> MenuState<HomeComponents> {
+    let mut menu = MenuState::new([
+        (HomeComponents::Continue, "Continue"),
+        (HomeComponents::NewGame, "New Game"),
+        (HomeComponents::Options, "Options"),
+        (HomeComponents::Quit, "Quit"),
+    ]);
+    menu.set_enabled(HomeComponents::Continue, false);
+    menu
+}
+
+pub struct HomeWidget<'a> {
+    pub theme: &'a Scheme,
+    pub title: &'a str,
+    /// Shown instead of `title` when the art doesn't fit.
+    pub title_text: &'a str,
+}
 
 #[derive(Debug, Clone, Event, PartialEq, Eq)]
 pub enum HomeEvent {
     MouseEvent(MouseEvent),
     KeyEvent(KeyEvent),
+    Action(GameAction),
 }
 
-impl WidgetRef for HomeWidget {
-    fn render_ref(&self, area: Rect, buf: &mut Buffer) {        
-        Block::default()
-            .title("Home")
-            .borders(Borders::ALL)
-            .render_ref(area, buf);
+impl From<InputEvent> for HomeEvent {
+    fn from(event: InputEvent) -> Self {
+        match event {
+            InputEvent::Key(key_event) => HomeEvent::KeyEvent(key_event),
+            InputEvent::Mouse(mouse_event) => HomeEvent::MouseEvent(mouse_event),
+            InputEvent::Action(action) => HomeEvent::Action(action),
+        }
+    }
+}
+
+impl StatefulWidgetRef for HomeWidget<'_> {
+    type State = MenuState<HomeComponents>;
+
+    fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let block = Block::bordered()
+            .border_type(block::BorderType::Rounded)
+            .border_style(self.theme.block())
+            .style(self.theme.data());
+        let inner = block.inner(area);
+        block.render_ref(area, buf);
+
+        let art = Text::from(self.title);
+        let title = if art.width() as u16 <= inner.width {
+            art
+        } else {
+            Text::from(self.title_text)
+        };
+        let menu = Menu::new(self.theme).spacing(0);
+        let [title_area, menu_area, footer_area] = Layout::vertical([
+            Constraint::Length(title.height() as u16 + 1),
+            Constraint::Length(menu.height(state.items.len())),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::SpaceAround)
+        .areas(inner);
+
+        // centered as a whole, so the lines of the art stay aligned
+        let [title_area] = Layout::horizontal([Constraint::Length(title.width() as u16)])
+            .flex(Flex::Center)
+            .areas(title_area);
+        Paragraph::new(title)
+            .style(self.theme.block_title())
+            .render(title_area, buf);
+
+        let [menu_area] = Layout::horizontal([Constraint::Length(25)])
+            .flex(Flex::Center)
+            .areas(menu_area);
+        menu.render_ref(menu_area, buf, state);
+
+        Paragraph::new(VERSION)
+            .style(self.theme.data())
+            .alignment(Alignment::Center)
+            .render(footer_area, buf);
     }
 }
 
@@ -32,52 +165,93 @@ pub struct HomePlugin;
 
 impl Plugin for HomePlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<HomeEvent>()            
-            .add_systems(PreUpdate, home_events_handler)
+        app.route_input::<HomeEvent>(Screen::Home)
+            .add_event::<MenuActivated<HomeComponents>>()
+            .init_resource::<TitleArt>()
+            .insert_resource(home_menu())
+            .add_systems(OnEnter(Screen::Home), reset_home_menu)
+            .add_systems(
+                PreUpdate,
+                (home_events_handler, home_menu_activated)
+                    .chain()
+                    .in_set(InputRoutingSet::Handle),
+            )
             .add_systems(Update, render_home.pipe(exit_on_error));
     }
 }
 
 fn render_home(
-    app_state: Res<State<AppState>>,
-    mut context: ResMut<RatatuiContext>,
+    screen: Res<State<Screen>>,
+    mut context: ResMut<TerminalContext>,
+    mut menu: ResMut<MenuState<HomeComponents>>,
+    settings: Res<Settings>,
+    title_art: Res<TitleArt>,
+    theme: Res<Theme>,
 ) -> color_eyre::Result<()> {
-    let app_state = app_state.get();
-    if app_state != &AppState::Home {
+    if screen.get() != &Screen::Home {
         return Ok(());
     }
     context.draw(|frame| {
         let area = frame.area();
-        frame.render_widget_ref(HomeWidget, area);
+        let widget = HomeWidget {
+            theme: &theme,
+            title: title_art.for_border_set(settings.video.border_set),
+            title_text: &title_art.text,
+        };
+        frame.render_stateful_widget_ref(widget, area, &mut menu);
+        apply_video_settings(&settings.video, frame.buffer_mut());
     })?;
     Ok(())
 }
 
-fn home_events_handler(    
-    mut app_state: ResMut<NextState<AppState>>,
+fn reset_home_menu(mut menu: ResMut<MenuState<HomeComponents>>) {
+    menu.reset();
+}
+
+fn home_events_handler(
+    mut navigate: EventWriter<Navigate>,
     mut home_events: EventReader<HomeEvent>,
+    mut menu: ResMut<MenuState<HomeComponents>>,
+    mut activated: EventWriter<MenuActivated<HomeComponents>>,
     mut app_exit: EventWriter<AppExit>,
 ) {
     for event in home_events.read() {
-        match event {
-            HomeEvent::MouseEvent(mouse_event) => {
-                // nothing
+        let id = match event {
+            HomeEvent::MouseEvent(mouse_event) => menu.handle_mouse(mouse_event),
+            HomeEvent::KeyEvent(key_event) => menu.handle_key(key_event),
+            HomeEvent::Action(GameAction::Quit) => {
+                app_exit.send_default();
+                None
+            }
+            HomeEvent::Action(GameAction::OpenOptions) => {
+                navigate.send(Navigate::Push(MenuScreen::Options));
+                None
+            }
+            HomeEvent::Action(action) => menu.handle_action(*action),
+        };
+        if let Some(id) = id {
+            activated.send(MenuActivated(id));
+        }
+    }
+}
+
+fn home_menu_activated(
+    mut app_state: ResMut<NextState<AppState>>,
+    mut navigate: EventWriter<Navigate>,
+    mut activated: EventReader<MenuActivated<HomeComponents>>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    for MenuActivated(id) in activated.read() {
+        match id {
+            HomeComponents::Continue => {
+                info!("There are no saved games yet");
+            }
+            HomeComponents::NewGame => app_state.set(AppState::InGame),
+            HomeComponents::Options => {
+                navigate.send(Navigate::Push(MenuScreen::Options));
             }
-            HomeEvent::KeyEvent(key_event) => {
-                match key_event.kind {
-                    KeyEventKind::Release => {
-                        match key_event.code {
-                            crossterm::event::KeyCode::Char('q') => {
-                                app_exit.send_default();
-                            }
-                            crossterm::event::KeyCode::Esc => {
-                                app_state.set(AppState::Options);
-                            }
-                            _ => {}
-                        }
-                    }
-                    _ => {}
-                }
+            HomeComponents::Quit => {
+                app_exit.send_default();
             }
         }
     }
@@ -1,25 +1,91 @@
-use crate::mini_salsa::theme::THEME;
+use crate::{
+    config::{
+        key_bindings::{format_key_chord, format_key_sequence, ControlSettings},
+        settings::Settings,
+        theme::Theme,
+    },
+    input::{
+        actions::GameAction,
+        routing::{InputCapture, InputEvent, InputRoutingAppExt, InputRoutingSet},
+    },
+    mini_salsa::theme::Scheme,
+    states::screen::Screen,
+    terminal::{context::TerminalContext, error::exit_on_error},
+    widgets::{
+        settings_page::{SettingsCommand, SettingsDraft},
+        video::apply_video_settings,
+    },
+};
 use anyhow::Result;
-use bevy::{input::keyboard::Key, prelude::*, utils::tracing::span};
-use crossterm::event::{KeyCode, KeyModifiers};
+use bevy::prelude::*;
+use bevy_ratatui::event::{KeyEvent, MouseEvent};
+use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
 use rat_ftable::{
     selection::RowSelection,
     textdata::{Cell, Row},
-    Table, TableContext, TableData, TableSelection, TableState,
+    Table, TableContext, TableData, TableState,
 };
 use rat_scrolled::Scroll;
 use ratatui::{widgets::Widget,
     buffer::Buffer,
     layout::{Constraint, Flex, Layout, Position, Rect},
-    style::{Color, Style},
+    style::Style,
     text::Span,
-    widgets::{Block, BorderType, Borders, StatefulWidgetRef, StatefulWidget, WidgetRef, block},
+    widgets::{Block, Paragraph, StatefulWidgetRef, StatefulWidget, block},
 };
-use std::{collections::HashMap, fmt::Display};
+use std::{collections::HashMap, time::Duration};
+
+/// Longest name a user profile can be given.
+const MAX_PROFILE_NAME: usize = 24;
+
+/// A key together with its modifiers, e.g. `Ctrl+s`.
+pub type KeyChord = (crossterm::event::KeyCode, crossterm::event::KeyModifiers);
+
+/// Chords pressed one after the other, e.g. `g g` or `Ctrl+x Ctrl+s`.
+///
+/// Most bindings are a single chord. A sequence is never empty.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeySequence(Vec<KeyChord>);
+
+impl KeySequence {
+    /// `None` for an empty list of chords.
+    pub fn new(chords: Vec<KeyChord>) -> Option<Self> {
+        (!chords.is_empty()).then_some(Self(chords))
+    }
+
+    pub fn chords(&self) -> &[KeyChord] {
+        &self.0
+    }
+
+    /// The chord that completes the sequence.
+    pub fn last(&self) -> KeyChord {
+        *self.0.last().expect("a key sequence is never empty")
+    }
+
+    /// Whether `self` is the start of the longer sequence `other`.
+    pub fn is_prefix_of(&self, other: &KeySequence) -> bool {
+        self.0.len() < other.0.len() && other.0.starts_with(&self.0)
+    }
+
+    /// The sequence with `chord` pressed after it.
+    pub fn then(&self, chord: KeyChord) -> Self {
+        let mut chords = self.0.clone();
+        chords.push(chord);
+        Self(chords)
+    }
+}
+
+impl From<KeyChord> for KeySequence {
+    fn from(chord: KeyChord) -> Self {
+        Self(vec![chord])
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct KeyBinding {
     pub defined_action_description: String,
+    /// Chords pressed before `user_key` when the binding is a sequence like `g g`.
+    pub user_key_prefix: Vec<KeyChord>,
     pub user_key: crossterm::event::KeyCode,
     pub user_key_modifiers: crossterm::event::KeyModifiers,
     pub system_key: crossterm::event::KeyCode,
@@ -31,6 +97,7 @@ impl Default for KeyBinding {
     fn default() -> Self {
         Self {
             defined_action_description: String::new(),
+            user_key_prefix: Vec::new(),
             user_key: crossterm::event::KeyCode::Null,
             user_key_modifiers: crossterm::event::KeyModifiers::empty(),
             system_key: crossterm::event::KeyCode::Null,
@@ -40,118 +107,222 @@ impl Default for KeyBinding {
     }
 }
 
+impl KeyBinding {
+    /// Everything the player presses for the binding, the prefix and the user key.
+    pub fn user_sequence(&self) -> KeySequence {
+        KeySequence(self.user_key_prefix.clone()).then((self.user_key, self.user_key_modifiers))
+    }
+
+    pub fn set_user_sequence(&mut self, sequence: &KeySequence) {
+        let (prefix, _) = sequence.0.split_at(sequence.0.len() - 1);
+        self.user_key_prefix = prefix.to_vec();
+        (self.user_key, self.user_key_modifiers) = sequence.last();
+    }
+}
+
 #[derive(Debug, Default, Resource, Deref, DerefMut, Clone)]
 pub struct KeyBindingsWidget {
-    pub key_bindings:
-        HashMap<(crossterm::event::KeyCode, crossterm::event::KeyModifiers), KeyBinding>,
-    pub rect: Rect,
+    pub key_bindings: HashMap<KeySequence, KeyBinding>,
     #[deref]
     pub id: uuid::Uuid,
-    pub lead_row: Option<usize>,
 }
 
 impl KeyBindingsWidget {
     pub fn new() -> Self {
         Self {
             key_bindings: HashMap::new(),
-            rect: Rect::default(),
             id: uuid::Uuid::new_v4(),
-            lead_row: None,
         }
     }
 
-    pub fn select(&mut self, row: usize) {
-        self.lead_row = Some(row);
+    /// Key bindings in the order they are shown in the table.
+    pub fn sorted_key_bindings(&self) -> Vec<KeyBinding> {
+        let mut key_bindings: Vec<KeyBinding> = self.key_bindings.values().cloned().collect();
+        key_bindings.sort_by(|a, b| {
+            a.is_customizable
+                .cmp(&b.is_customizable)
+                .then_with(|| a.defined_action_description.cmp(&b.defined_action_description))
+                .then_with(|| {
+                    format_key_sequence(&a.user_sequence())
+                        .cmp(&format_key_sequence(&b.user_sequence()))
+                })
+        });
+        key_bindings
+    }
+
+    /// Whether `sequence` is the start of a longer bound sequence, so more keys may follow.
+    pub fn is_sequence_prefix(&self, sequence: &KeySequence) -> bool {
+        self.key_bindings.keys().any(|bound| sequence.is_prefix_of(bound))
     }
-}
 
-impl TableSelection for KeyBindingsWidget {
-    fn is_selected_row(&self, row: usize) -> bool {
-        self.lead_row == Some(row)
+    /// A binding `sequence` cannot be used next to: one that starts with it, or
+    /// one it starts with. Either would make the shorter one fire too early.
+    ///
+    /// The binding on `ignored` is skipped, it is the one being moved.
+    pub fn prefix_conflict(
+        &self,
+        sequence: &KeySequence,
+        ignored: Option<&KeySequence>,
+    ) -> Option<&KeyBinding> {
+        self.key_bindings
+            .iter()
+            .filter(|(bound, _)| Some(*bound) != ignored)
+            .find(|(bound, _)| sequence.is_prefix_of(bound) || bound.is_prefix_of(sequence))
+            .map(|(_, key_binding)| key_binding)
+    }
+
+    /// Moves the binding on `from` to the unused user key `to`.
+    pub fn rebind_user_key(
+        &mut self,
+        from: &KeySequence,
+        to: &KeySequence,
+    ) -> Result<(), anyhow::Error> {
+        if from == to {
+            return Ok(());
+        }
+        self.customizable_binding(from)?;
+        if self.key_bindings.contains_key(to) {
+            return Err(anyhow::Error::msg("Key binding already exists"));
+        }
+        self.check_prefix_conflict(to, Some(from))?;
+        let mut key_binding = self.key_bindings.remove(from).expect("binding checked above");
+        key_binding.set_user_sequence(to);
+        self.key_bindings.insert(to.clone(), key_binding);
+        Ok(())
+    }
+
+    /// Exchanges the user keys of the two bindings.
+    pub fn swap_user_keys(
+        &mut self,
+        a: &KeySequence,
+        b: &KeySequence,
+    ) -> Result<(), anyhow::Error> {
+        if a == b {
+            return Ok(());
+        }
+        self.customizable_binding(a)?;
+        self.customizable_binding(b)?;
+        let mut first = self.key_bindings.remove(a).expect("binding checked above");
+        let mut second = self.key_bindings.remove(b).expect("binding checked above");
+        first.set_user_sequence(b);
+        second.set_user_sequence(a);
+        self.key_bindings.insert(b.clone(), first);
+        self.key_bindings.insert(a.clone(), second);
+        Ok(())
     }
 
-    fn is_selected_column(&self, _column: usize) -> bool {
-        false
+    /// Moves the binding on `from` to `to`, which is used by another binding.
+    ///
+    /// The other binding falls back to its system key, which has to be free.
+    pub fn replace_user_key(
+        &mut self,
+        from: &KeySequence,
+        to: &KeySequence,
+    ) -> Result<(), anyhow::Error> {
+        if from == to {
+            return Ok(());
+        }
+        self.customizable_binding(from)?;
+        let other = self.customizable_binding(to)?;
+        let fallback = KeySequence::from((other.system_key, other.system_key_modifiers));
+        if fallback == *to || (fallback != *from && self.key_bindings.contains_key(&fallback)) {
+            return Err(anyhow::Error::msg(
+                "The other action has no free key to fall back to, swap them instead",
+            ));
+        }
+        let conflict = self
+            .key_bindings
+            .iter()
+            .filter(|(bound, _)| *bound != from && *bound != to)
+            .any(|(bound, _)| fallback.is_prefix_of(bound) || bound.is_prefix_of(&fallback));
+        if conflict {
+            return Err(anyhow::Error::msg(
+                "The key the other action falls back to starts a sequence, swap them instead",
+            ));
+        }
+        let mut key_binding = self.key_bindings.remove(from).expect("binding checked above");
+        let mut other = self.key_bindings.remove(to).expect("binding checked above");
+        key_binding.set_user_sequence(to);
+        other.set_user_sequence(&fallback);
+        self.key_bindings.insert(to.clone(), key_binding);
+        self.key_bindings.insert(fallback, other);
+        Ok(())
     }
 
-    fn is_selected_cell(&self, _column: usize, _row: usize) -> bool {
-        false
+    fn check_prefix_conflict(
+        &self,
+        sequence: &KeySequence,
+        ignored: Option<&KeySequence>,
+    ) -> Result<(), anyhow::Error> {
+        match self.prefix_conflict(sequence, ignored) {
+            Some(key_binding) => Err(anyhow::Error::msg(format!(
+                "{} clashes with {} of '{}', one starts the other",
+                format_key_sequence(sequence),
+                format_key_sequence(&key_binding.user_sequence()),
+                key_binding.defined_action_description
+            ))),
+            None => Ok(()),
+        }
     }
 
-    fn lead_selection(&self) -> Option<(usize, usize)> {
-        self.lead_row.map(|v| (0, v))
+    fn customizable_binding(&self, user_key: &KeySequence) -> Result<&KeyBinding, anyhow::Error> {
+        match self.key_bindings.get(user_key) {
+            Some(key_binding) if key_binding.is_customizable => Ok(key_binding),
+            Some(key_binding) => Err(anyhow::Error::msg(format!(
+                "'{}' cannot be changed",
+                key_binding.defined_action_description
+            ))),
+            None => Err(anyhow::Error::msg("No matching key binding found")),
+        }
     }
 }
 
-fn convert_key_code_and_modifiers_to_string(
-    key_code: KeyCode,
-    key_modifiers: KeyModifiers,
-) -> String {
-    let key_code_string: String = match key_code {
-        KeyCode::Null => "Null".to_string(),
-        KeyCode::Enter => "Enter".to_string(),
-        KeyCode::Esc => "Esc".to_string(),
-        KeyCode::Backspace => "Backspace".to_string(),
-        KeyCode::Left => "Left".to_string(),
-        KeyCode::Right => "Right".to_string(),
-        KeyCode::Up => "Up".to_string(),
-        KeyCode::Down => "Down".to_string(),
-        KeyCode::Home => "Home".to_string(),
-        KeyCode::End => "End".to_string(),
-        KeyCode::PageUp => "PageUp".to_string(),
-        KeyCode::PageDown => "PageDown".to_string(),
-        KeyCode::Tab => "Tab".to_string(),
-        KeyCode::BackTab => "BackTab".to_string(),
-        KeyCode::Delete => "Delete".to_string(),
-        KeyCode::Insert => "Insert".to_string(),
-        KeyCode::F(n) => format!("F{}", n),
-        KeyCode::Char(c) => format!("{}", c),
-        KeyCode::NumLock => "NumLock".to_string(),
-        KeyCode::ScrollLock => "ScrollLock".to_string(),
-        KeyCode::CapsLock => "CapsLock".to_string(),
-        KeyCode::PrintScreen => "PrintScreen".to_string(),
-        KeyCode::Pause => "Pause".to_string(),
-        KeyCode::Menu => "Menu".to_string(),
-        KeyCode::KeypadBegin => "KeypadBegin".to_string(),
-        KeyCode::Media(c) => format!("Media({})", c),
-        KeyCode::Modifier(c) => format!("Modifier({})", c),
-    };
-
-    let mut key_modifiers_string = "";
-    if key_modifiers.contains(KeyModifiers::CONTROL) {
-        key_modifiers_string = "Ctrl";
-    }
-    if key_modifiers.contains(KeyModifiers::ALT) {
-        key_modifiers_string = "Alt";
-    }
-    if key_modifiers.contains(KeyModifiers::SHIFT) {
-        key_modifiers_string = "Shift";
-    }
-    if key_modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::ALT) {
-        key_modifiers_string = "Ctrl+Alt";
-    }
-    if key_modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT) {
-        key_modifiers_string = "Ctrl+Shift";
-    }
-    if key_modifiers.contains(KeyModifiers::ALT | KeyModifiers::SHIFT) {
-        key_modifiers_string = "Alt+Shift";
-    }
-    if key_modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT) {
-        key_modifiers_string = "Ctrl+Alt+Shift";
-    }
-
-    format!("{}+{}", key_code_string, key_modifiers_string)
+/// Progress of changing a binding from the table.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum RebindMode {
+    #[default]
+    Idle,
+    /// Collecting the chords of the new keys for the binding on `user_key`.
+    Capturing {
+        user_key: KeySequence,
+        captured: Vec<KeyChord>,
+        /// When the last chord was pressed.
+        pressed_at: Duration,
+    },
+    /// The `captured` keys are already used by another binding.
+    Conflict { user_key: KeySequence, captured: KeySequence },
+    /// The last rebind attempt was refused.
+    Refused { reason: String },
+}
+
+/// What a key press did in the rebind flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebindOutcome {
+    /// The key is not part of the rebind flow.
+    Ignored,
+    /// The flow used the key without changing any binding.
+    Handled,
+    /// A binding was changed.
+    Rebound,
 }
 
+/// Table and rebind state of the key bindings screen.
+///
+/// The table state is not `Send`, so this lives in a non-send resource.
 #[derive(Debug, Default, Clone)]
 pub struct KeyBindingsState {
     table_state: TableState<RowSelection>,
+    pub rebind: RebindMode,
+    /// Name being typed to save the bindings as a profile.
+    pub profile_name: Option<String>,
 }
 
 impl KeyBindingsState {
     pub fn new() -> Self {
         Self {
             table_state: TableState::default(),
+            rebind: RebindMode::Idle,
+            profile_name: None,
         }
     }
 
@@ -167,6 +338,264 @@ impl KeyBindingsState {
         let item_index = self.table_state.row_at_clicked((position.x, position.y));
         self.table_state.select(item_index);
     }
+
+    /// Moves the selection up by `n` rows, keeping it visible.
+    pub fn select_previous(&mut self, n: usize) {
+        self.table_state.move_up(n);
+    }
+
+    /// Moves the selection down by `n` rows, keeping it visible.
+    pub fn select_next(&mut self, n: usize) {
+        self.table_state.move_down(n);
+    }
+
+    pub fn page_len(&self) -> usize {
+        self.table_state.page_len()
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.table_state.scroll_up(self.table_state.row_scroll_by());
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.table_state.scroll_down(self.table_state.row_scroll_by());
+    }
+
+    /// Whether the rebind flow is waiting for input.
+    pub fn is_rebinding(&self) -> bool {
+        self.rebind != RebindMode::Idle
+    }
+
+    /// Starts capturing a new key for the selected row.
+    pub fn begin_rebind(&mut self, widget: &KeyBindingsWidget) {
+        let Some(row) = self.table_state.selected() else {
+            return;
+        };
+        let Some(key_binding) = widget.sorted_key_bindings().into_iter().nth(row) else {
+            return;
+        };
+        self.rebind = if key_binding.is_customizable {
+            RebindMode::Capturing {
+                user_key: key_binding.user_sequence(),
+                captured: Vec::new(),
+                pressed_at: Duration::ZERO,
+            }
+        } else {
+            RebindMode::Refused {
+                reason: format!(
+                    "'{}' cannot be changed",
+                    key_binding.defined_action_description
+                ),
+            }
+        };
+    }
+
+    pub fn cancel_rebind(&mut self) {
+        self.rebind = RebindMode::Idle;
+        self.profile_name = None;
+    }
+
+    /// Whether the screen takes every key, so Esc does not leave it.
+    pub fn is_capturing(&self) -> bool {
+        self.is_rebinding() || self.profile_name.is_some()
+    }
+
+    /// Feeds a key press into the profile name being typed.
+    ///
+    /// Returns the name once Enter is pressed, Esc drops it.
+    pub fn handle_profile_name_key(
+        &mut self,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> Option<String> {
+        let name = self.profile_name.as_mut()?;
+        match code {
+            KeyCode::Enter => return self.profile_name.take(),
+            KeyCode::Esc => self.profile_name = None,
+            KeyCode::Backspace => {
+                name.pop();
+            }
+            KeyCode::Char(c)
+                if (modifiers - KeyModifiers::SHIFT).is_empty()
+                    && name.chars().count() < MAX_PROFILE_NAME =>
+            {
+                name.push(c);
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Feeds a key press at `now` into the rebind flow.
+    ///
+    /// Enter starts capturing for the selected row. The chords pressed next become the
+    /// new binding once Enter is pressed after them, or once no chord came for a while,
+    /// see [`KeyBindingsState::finish_capture_after`].
+    /// On a conflict Enter replaces the other binding, `s` swaps both and Esc cancels.
+    pub fn handle_rebind_key(
+        &mut self,
+        widget: &mut KeyBindingsWidget,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+        now: Duration,
+    ) -> RebindOutcome {
+        match self.rebind.clone() {
+            RebindMode::Idle => {
+                if code == KeyCode::Enter && modifiers.is_empty() {
+                    self.begin_rebind(widget);
+                    return RebindOutcome::Handled;
+                }
+                RebindOutcome::Ignored
+            }
+            RebindMode::Refused { .. } => {
+                self.rebind = RebindMode::Idle;
+                RebindOutcome::Handled
+            }
+            RebindMode::Capturing {
+                user_key,
+                mut captured,
+                ..
+            } => {
+                if let KeyCode::Modifier(_) = code {
+                    // wait for the actual key of the chord
+                } else if code == KeyCode::Esc && modifiers.is_empty() {
+                    self.rebind = RebindMode::Idle;
+                } else if code == KeyCode::Enter && modifiers.is_empty() && !captured.is_empty() {
+                    return self.finish_capture(widget, user_key, captured);
+                } else {
+                    captured.push((code, modifiers));
+                    self.rebind = RebindMode::Capturing {
+                        user_key,
+                        captured,
+                        pressed_at: now,
+                    };
+                }
+                RebindOutcome::Handled
+            }
+            RebindMode::Conflict { user_key, captured } => {
+                match (code, modifiers.is_empty()) {
+                    (KeyCode::Enter, true) => {
+                        return self.finish_rebind(widget.replace_user_key(&user_key, &captured));
+                    }
+                    (KeyCode::Char('s'), true) => {
+                        return self.finish_rebind(widget.swap_user_keys(&user_key, &captured));
+                    }
+                    (KeyCode::Esc, true) => {
+                        self.rebind = RebindMode::Idle;
+                    }
+                    _ => {}
+                }
+                RebindOutcome::Handled
+            }
+        }
+    }
+
+    /// Ends the capture once `timeout` passed since the last chord, like a key
+    /// sequence that is not continued.
+    pub fn finish_capture_after(
+        &mut self,
+        widget: &mut KeyBindingsWidget,
+        now: Duration,
+        timeout: Duration,
+    ) -> RebindOutcome {
+        match self.rebind.clone() {
+            RebindMode::Capturing {
+                user_key,
+                captured,
+                pressed_at,
+            } if !captured.is_empty() && now.saturating_sub(pressed_at) > timeout => {
+                self.finish_capture(widget, user_key, captured)
+            }
+            _ => RebindOutcome::Ignored,
+        }
+    }
+
+    fn finish_capture(
+        &mut self,
+        widget: &mut KeyBindingsWidget,
+        user_key: KeySequence,
+        captured: Vec<KeyChord>,
+    ) -> RebindOutcome {
+        let captured = KeySequence::new(captured).expect("at least one chord was captured");
+        if captured == user_key {
+            // pressed the keys it already has
+            self.rebind = RebindMode::Idle;
+        } else if widget.key_bindings.contains_key(&captured) {
+            self.rebind = RebindMode::Conflict { user_key, captured };
+        } else {
+            return self.finish_rebind(widget.rebind_user_key(&user_key, &captured));
+        }
+        RebindOutcome::Handled
+    }
+
+    fn finish_rebind(&mut self, result: Result<(), anyhow::Error>) -> RebindOutcome {
+        match result {
+            Ok(()) => {
+                self.rebind = RebindMode::Idle;
+                RebindOutcome::Rebound
+            }
+            Err(err) => {
+                self.rebind = RebindMode::Refused {
+                    reason: err.to_string(),
+                };
+                RebindOutcome::Handled
+            }
+        }
+    }
+
+    /// The hint line shown below the table.
+    fn status_line(&self, widget: &KeyBindingsWidget, theme: &Scheme) -> (String, Style) {
+        let description = |user_key: &KeySequence| {
+            widget
+                .key_bindings
+                .get(user_key)
+                .map(|key_binding| key_binding.defined_action_description.clone())
+                .unwrap_or_default()
+        };
+        if let Some(name) = &self.profile_name {
+            return (
+                format!("Profile name: {}_  Enter: save  Esc: cancel", name),
+                theme.focus(),
+            );
+        }
+        match &self.rebind {
+            RebindMode::Idle => (
+                "Enter: change  p: profile  n: save profile  a: apply  r: reset  Esc: back"
+                    .to_string(),
+                theme.status_style(),
+            ),
+            RebindMode::Capturing {
+                user_key, captured, ..
+            } if captured.is_empty() => (
+                format!("Press the keys for '{}'  Esc: cancel", description(user_key)),
+                theme.focus(),
+            ),
+            RebindMode::Capturing {
+                user_key, captured, ..
+            } => (
+                format!(
+                    "Keys for '{}': {}_  Enter: done  Esc: cancel",
+                    description(user_key),
+                    captured
+                        .iter()
+                        .map(|(code, modifiers)| format_key_chord(*code, *modifiers))
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+                theme.focus(),
+            ),
+            RebindMode::Conflict { user_key, captured } => (
+                format!(
+                    "{} is used by '{}'. Enter: replace  s: swap with '{}'  Esc: cancel",
+                    format_key_sequence(captured),
+                    description(captured),
+                    description(user_key),
+                ),
+                theme.red(2),
+            ),
+            RebindMode::Refused { reason } => (format!("{}  (any key)", reason), theme.red(2)),
+        }
+    }
 }
 
 pub trait KeyBindingsTrait {
@@ -185,7 +614,9 @@ impl KeyBindingsTrait for KeyBindingsWidget {
         user_key_modifiers: crossterm::event::KeyModifiers,
     ) -> Result<(crossterm::event::KeyCode, crossterm::event::KeyModifiers), anyhow::Error> {
         // match user_key and modifiers to system_key and modifiers
-        let matched = self.key_bindings.get(&(user_key, user_key_modifiers));
+        let matched = self
+            .key_bindings
+            .get(&KeySequence::from((user_key, user_key_modifiers)));
         match matched {
             Some(key_binding) => Ok((key_binding.system_key, key_binding.system_key_modifiers)),
             None => Err(anyhow::Error::msg("No matching key binding found")),
@@ -193,31 +624,40 @@ impl KeyBindingsTrait for KeyBindingsWidget {
     }
 
     fn add_custom_key_binding(&mut self, key_binding: KeyBinding) -> Result<(), anyhow::Error> {
+        let user_key = key_binding.user_sequence();
         // if key binding already exists, return error
-        if self
-            .key_bindings
-            .contains_key(&(key_binding.user_key, key_binding.user_key_modifiers))
-        {
+        if self.key_bindings.contains_key(&user_key) {
             return Err(anyhow::Error::msg("Key binding already exists"));
         }
+        // a sequence and its start cannot both be bound
+        self.check_prefix_conflict(&user_key, None)?;
         // add custom key binding
-        self.key_bindings.insert(
-            (key_binding.user_key, key_binding.user_key_modifiers),
-            key_binding,
-        );
+        self.key_bindings.insert(user_key, key_binding);
         Ok(())
     }
 }
 
-impl StatefulWidgetRef for KeyBindingsWidget {
+/// Draws the [`KeyBindingsWidget`] with a theme.
+pub struct KeyBindingsView<'a> {
+    pub key_bindings: &'a KeyBindingsWidget,
+    /// Shown in the title when the bindings are those of a profile.
+    pub profile: Option<&'a str>,
+    pub theme: &'a Scheme,
+}
+
+impl StatefulWidgetRef for KeyBindingsView<'_> {
     type State = KeyBindingsState;
 
     fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let (status, style) = state.status_line(self.key_bindings, self.theme);
+        let status = wrap_hints(&status, area.width);
 
-        // chunk in the middle of the screen
-        let sub_area = Layout::default()
-            .constraints([Constraint::Percentage(100)])
-            .split(area)[0];
+        // chunk in the middle of the screen, with the hint lines below the table
+        let [sub_area, status_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(status.len() as u16),
+        ])
+        .areas(area);
 
         // add table rectangle for key bindings
         let l0 = Layout::horizontal([Constraint::Percentage(100)])
@@ -225,7 +665,7 @@ impl StatefulWidgetRef for KeyBindingsWidget {
             .split(sub_area);
 
         // prepare dataslice for keybindings
-        struct DataSlice<'a>(&'a [KeyBinding]);
+        struct DataSlice<'a>(&'a [KeyBinding], &'a Scheme);
 
         impl<'a> TableData<'a> for DataSlice<'a> {
             fn rows(&self) -> usize {
@@ -235,9 +675,9 @@ impl StatefulWidgetRef for KeyBindingsWidget {
             fn row_style(&self, _row: usize) -> Option<Style> {
                 self.0.get(_row).map(|d| {
                     if d.is_customizable {
-                        Style::default().fg(Color::Yellow)
+                        Style::default().fg(self.1.yellow[3])
                     } else {
-                        Style::default().fg(Color::White)
+                        Style::default().fg(self.1.white[0])
                     }
                 })
             }
@@ -257,22 +697,17 @@ impl StatefulWidgetRef for KeyBindingsWidget {
                             span.render(area, buf);
                         }
                         1 => {
-                            // convert KeyBinding KeyCode and KeyModifiers to string
-                            let system_key_binding = convert_key_code_and_modifiers_to_string(
-                                d.system_key,
-                                d.system_key_modifiers,
-                            );
-                            let user_key_binding = convert_key_code_and_modifiers_to_string(
-                                d.user_key,
-                                d.user_key_modifiers,
-                            );
-                            if user_key_binding.is_empty() {
+                            // show the system key when no user key is assigned
+                            if d.user_key == KeyCode::Null {
+                                let system_key_binding =
+                                    format_key_chord(d.system_key, d.system_key_modifiers);
                                 let span = Span::from(system_key_binding);
-                                span.style(Style::default().fg(Color::Gray))
+                                span.style(Style::default().fg(self.1.gray[3]))
                                     .render(area, buf);
                             } else {
+                                let user_key_binding = format_key_sequence(&d.user_sequence());
                                 let span = Span::from(user_key_binding);
-                                span.style(Style::default().fg(Color::White))
+                                span.style(Style::default().fg(self.1.white[0]))
                                     .render(area, buf);
                             }
                         }
@@ -284,13 +719,16 @@ impl StatefulWidgetRef for KeyBindingsWidget {
             }
         }
 
-        // sort key_binding in key_bindings by is_customizable
-        let mut key_bindings: Vec<KeyBinding> = self.key_bindings.values().cloned().collect();
-        key_bindings.sort_by(|a, b| a.is_customizable.cmp(&b.is_customizable));
+        // rows in display order, customizable bindings last
+        let key_bindings = self.key_bindings.sorted_key_bindings();
+        let title = match self.profile {
+            Some(profile) => format!("Key Bindings ({})", profile),
+            None => "Key Bindings".to_string(),
+        };
 
         // define table
         Table::default()
-            .data(DataSlice(key_bindings.as_slice()))
+            .data(DataSlice(key_bindings.as_slice(), self.theme))
             .widths([Constraint::Percentage(70), Constraint::Percentage(30)])
             .column_spacing(1)
             .header(Row::new([Cell::from("Action"), Cell::from("Key Binding")]))
@@ -298,12 +736,206 @@ impl StatefulWidgetRef for KeyBindingsWidget {
             .block(
                 Block::bordered()
                 .border_type(block::BorderType::Rounded)
-                .border_style(THEME.block())
-                .title_style(THEME.block_title())
-                .title("Key Bindings"),
+                .border_style(self.theme.block())
+                .title_style(self.theme.block_title())
+                .title(title),
             )
             .vscroll(Scroll::new())
-            .styles(THEME.table_style())
+            .styles(self.theme.table_style())
             .render(l0[0], buf, &mut state.table_state);
+
+        Paragraph::new(status.join("\n"))
+            .style(style)
+            .render(status_area, buf);
     }
 }
+
+/// Breaks a status line between its hints, which are separated by two spaces,
+/// so each line fits `width`. A hint wider than `width` gets a line of its own.
+fn wrap_hints(status: &str, width: u16) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for hint in status.split("  ") {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 2 + hint.chars().count() <= width as usize => {
+                line.push_str("  ");
+                line.push_str(hint);
+            }
+            _ => lines.push(hint.to_string()),
+        }
+    }
+    lines
+}
+
+pub struct KeyBindingsPlugin;
+
+impl Plugin for KeyBindingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.route_input::<KeyBindingsEvent>(Screen::KeyBindings)
+            .insert_non_send_resource(KeyBindingsState::new())
+            .add_systems(OnEnter(Screen::KeyBindings), reset_key_bindings_state)
+            .add_systems(OnExit(Screen::KeyBindings), release_input_capture)
+            .add_systems(
+                PreUpdate,
+                key_bindings_events_handler.in_set(InputRoutingSet::Handle),
+            )
+            .add_systems(Update, render_key_bindings.pipe(exit_on_error));
+    }
+}
+
+#[derive(Debug, Clone, Event, PartialEq, Eq)]
+pub enum KeyBindingsEvent {
+    MouseEvent(MouseEvent),
+    KeyEvent(KeyEvent),
+    Action(GameAction),
+}
+
+impl From<InputEvent> for KeyBindingsEvent {
+    fn from(event: InputEvent) -> Self {
+        match event {
+            InputEvent::Key(key_event) => KeyBindingsEvent::KeyEvent(key_event),
+            InputEvent::Mouse(mouse_event) => KeyBindingsEvent::MouseEvent(mouse_event),
+            InputEvent::Action(action) => KeyBindingsEvent::Action(action),
+        }
+    }
+}
+
+fn reset_key_bindings_state(mut state: NonSendMut<KeyBindingsState>) {
+    state.cancel_rebind();
+    if state.selected().is_none() {
+        state.select(0);
+    }
+}
+
+fn release_input_capture(mut capture: ResMut<InputCapture>) {
+    capture.set_if_neq(InputCapture(false));
+}
+
+/// Edits the controls of the [`SettingsDraft`], the bindings in use only change on Apply.
+fn key_bindings_events_handler(
+    mut events: EventReader<KeyBindingsEvent>,
+    mut state: NonSendMut<KeyBindingsState>,
+    mut draft: ResMut<SettingsDraft>,
+    mut commands: EventWriter<SettingsCommand>,
+    mut capture: ResMut<InputCapture>,
+    settings: Res<Settings>,
+    time: Res<Time<Real>>,
+) {
+    let mut key_bindings = draft.controls.to_key_bindings().widget;
+    let now = time.elapsed();
+    let timeout = settings.controls.sequence_timeout();
+    if state.finish_capture_after(&mut key_bindings, now, timeout) == RebindOutcome::Rebound {
+        draft.controls.bindings = ControlSettings::from_widget(&key_bindings).bindings;
+    }
+    // Actions follow the key they were mapped from, skip them if the rebind flow used that key.
+    let mut key_used = false;
+    for event in events.read() {
+        match event {
+            KeyBindingsEvent::KeyEvent(key_event) => {
+                if key_event.kind == KeyEventKind::Release {
+                    continue;
+                }
+                key_used = match key_event.code {
+                    _ if state.profile_name.is_some() => {
+                        let name =
+                            state.handle_profile_name_key(key_event.code, key_event.modifiers);
+                        if let Some(name) = name {
+                            if let Err(err) = draft.controls.save_profile(&name) {
+                                state.rebind = RebindMode::Refused {
+                                    reason: err.to_string(),
+                                };
+                            }
+                        }
+                        true
+                    }
+                    _ if state.is_rebinding() || key_event.code == KeyCode::Enter => {
+                        let outcome = state.handle_rebind_key(
+                            &mut key_bindings,
+                            key_event.code,
+                            key_event.modifiers,
+                            now,
+                        );
+                        if outcome == RebindOutcome::Rebound {
+                            draft.controls.bindings =
+                                ControlSettings::from_widget(&key_bindings).bindings;
+                        }
+                        outcome != RebindOutcome::Ignored
+                    }
+                    KeyCode::Char('p') if key_event.modifiers.is_empty() => {
+                        draft.controls.use_next_profile();
+                        key_bindings = draft.controls.to_key_bindings().widget;
+                        true
+                    }
+                    KeyCode::Char('n') if key_event.modifiers.is_empty() => {
+                        state.profile_name = Some(String::new());
+                        true
+                    }
+                    KeyCode::Char('a') if key_event.modifiers.is_empty() => {
+                        commands.send(SettingsCommand::Apply);
+                        true
+                    }
+                    KeyCode::Char('r') if key_event.modifiers.is_empty() => {
+                        // the default profile, like a new settings file
+                        draft.controls.bindings = ControlSettings::default().sorted().bindings;
+                        key_bindings = draft.controls.to_key_bindings().widget;
+                        true
+                    }
+                    KeyCode::PageUp => {
+                        let page = state.page_len().max(1);
+                        state.select_previous(page);
+                        true
+                    }
+                    KeyCode::PageDown => {
+                        let page = state.page_len().max(1);
+                        state.select_next(page);
+                        true
+                    }
+                    _ => false,
+                };
+            }
+            KeyBindingsEvent::Action(_) if key_used => {}
+            KeyBindingsEvent::Action(GameAction::Up) => state.select_previous(1),
+            KeyBindingsEvent::Action(GameAction::Down) => state.select_next(1),
+            // only reaches the page while it has changes, see `UnsavedChanges`
+            KeyBindingsEvent::Action(GameAction::Back) => {
+                commands.send(SettingsCommand::Cancel);
+            }
+            KeyBindingsEvent::Action(_) => {}
+            KeyBindingsEvent::MouseEvent(mouse_event) => match mouse_event.kind {
+                MouseEventKind::Down(MouseButton::Left) if !state.is_capturing() => {
+                    state.select_at_mouse(Position::new(mouse_event.column, mouse_event.row));
+                }
+                MouseEventKind::ScrollUp => state.scroll_up(),
+                MouseEventKind::ScrollDown => state.scroll_down(),
+                _ => {}
+            },
+        }
+    }
+    // Esc cancels a rebind instead of leaving the screen
+    capture.set_if_neq(InputCapture(state.is_capturing()));
+}
+
+fn render_key_bindings(
+    screen: Res<State<Screen>>,
+    mut context: ResMut<TerminalContext>,
+    draft: Res<SettingsDraft>,
+    mut state: NonSendMut<KeyBindingsState>,
+    settings: Res<Settings>,
+    theme: Res<Theme>,
+) -> color_eyre::Result<()> {
+    if screen.get() != &Screen::KeyBindings {
+        return Ok(());
+    }
+    let key_bindings = draft.controls.to_key_bindings().widget;
+    let profile = draft.controls.active_profile();
+    context.draw(|frame| {
+        let area = frame.area();
+        KeyBindingsView {
+            key_bindings: &key_bindings,
+            profile: profile.as_deref(),
+            theme: &theme,
+        }
+        .render_ref(area, frame.buffer_mut(), &mut state);
+        apply_video_settings(&settings.video, frame.buffer_mut());
+    })?;
+    Ok(())
+}
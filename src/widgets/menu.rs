@@ -0,0 +1,309 @@
+use std::marker::PhantomData;
+
+use bevy::prelude::*;
+use crossterm::event::{KeyCode, KeyEventKind, MouseButton, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Position, Rect},
+    style::Style,
+    widgets::{Block, Borders, Paragraph, StatefulWidgetRef, WidgetRef},
+};
+
+use crate::{input::actions::GameAction, mini_salsa::theme::Scheme};
+
+/// An entry of a [`MenuState`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem<T> {
+    pub id: T,
+    pub label: String,
+    /// Disabled items are shown but can't be focused or activated.
+    pub enabled: bool,
+}
+
+/// How a single menu item is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MenuItemState {
+    Normal,
+    Hovered,
+    Pressed,
+    Focused,
+    Disabled,
+}
+
+/// Sent when a menu item is activated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct MenuActivated<T>(pub T);
+
+/// Items of a menu together with their hover, pressed and focus state
+/// and the areas they were last drawn at.
+#[derive(Debug, Clone, Resource)]
+pub struct MenuState<T> {
+    pub items: Vec<MenuItem<T>>,
+    pub hovered: Option<usize>,
+    pub pressed: Option<usize>,
+    pub focused: Option<usize>,
+    rects: Vec<Rect>,
+}
+
+impl<T: Copy + PartialEq> MenuState<T> {
+    pub fn new<'a>(items: impl IntoIterator<Item = (T, &'a str)>) -> Self {
+        Self {
+            items: items
+                .into_iter()
+                .map(|(id, label)| MenuItem {
+                    id,
+                    label: label.to_string(),
+                    enabled: true,
+                })
+                .collect(),
+            hovered: None,
+            pressed: None,
+            focused: None,
+            rects: Vec::new(),
+        }
+    }
+
+    /// Enables or disables the item with the given id, moving focus away from it if needed.
+    pub fn set_enabled(&mut self, id: T, enabled: bool) {
+        for item in self.items.iter_mut().filter(|item| item.id == id) {
+            item.enabled = enabled;
+        }
+        if self.focused.is_some_and(|index| !self.is_enabled(index)) {
+            self.focused = None;
+            self.focus_next();
+        }
+    }
+
+    fn is_enabled(&self, index: usize) -> bool {
+        self.items.get(index).is_some_and(|item| item.enabled)
+    }
+
+    /// Index of the enabled item drawn at the given cell.
+    pub fn item_at(&self, x: u16, y: u16) -> Option<usize> {
+        self.rects
+            .iter()
+            .position(|rect| rect.contains(Position { x, y }))
+            .filter(|index| self.is_enabled(*index))
+    }
+
+    /// A pressed item only looks pressed while the mouse is still over it.
+    pub fn item_state(&self, index: usize) -> MenuItemState {
+        if !self.is_enabled(index) {
+            MenuItemState::Disabled
+        } else if self.pressed == Some(index) && self.hovered == Some(index) {
+            MenuItemState::Pressed
+        } else if self.hovered == Some(index) {
+            MenuItemState::Hovered
+        } else if self.focused == Some(index) {
+            MenuItemState::Focused
+        } else {
+            MenuItemState::Normal
+        }
+    }
+
+    /// Clears hover and pressed state, e.g. when the screen is entered again.
+    ///
+    /// Keyboard focus is kept, so returning to a menu leaves the last used item focused.
+    pub fn reset(&mut self) {
+        self.hovered = None;
+        self.pressed = None;
+        if self.focused.is_none_or(|index| !self.is_enabled(index)) {
+            self.focused = None;
+            self.focus_next();
+        }
+    }
+
+    /// Moves keyboard focus to the next enabled item, wrapping around at the end.
+    pub fn focus_next(&mut self) {
+        let len = self.items.len();
+        let start = self.focused.map_or(0, |index| index + 1);
+        if let Some(index) = (start..start + len)
+            .map(|index| index % len)
+            .find(|index| self.is_enabled(*index))
+        {
+            self.focus(index);
+        }
+    }
+
+    /// Moves keyboard focus to the previous enabled item, wrapping around at the start.
+    pub fn focus_prev(&mut self) {
+        let len = self.items.len();
+        let start = self.focused.unwrap_or(0) + len;
+        if let Some(index) = (1..=len)
+            .map(|offset| (start - offset) % len)
+            .find(|index| self.is_enabled(*index))
+        {
+            self.focus(index);
+        }
+    }
+
+    /// Focuses an enabled item from the keyboard, dropping a stale mouse hover.
+    pub fn focus(&mut self, index: usize) {
+        if self.is_enabled(index) {
+            self.focused = Some(index);
+            self.hovered = None;
+        }
+    }
+
+    /// Id of the focused item, which Enter or Space activates.
+    pub fn focused_id(&self) -> Option<T> {
+        self.focused.map(|index| self.items[index].id)
+    }
+
+    /// Moves focus for Up and Down and activates the focused item on Confirm.
+    ///
+    /// These arrive as actions, so they follow the key bindings.
+    /// Returns the id of the activated item.
+    pub fn handle_action(&mut self, action: GameAction) -> Option<T> {
+        match action {
+            GameAction::Up => self.focus_prev(),
+            GameAction::Down => self.focus_next(),
+            GameAction::Confirm => return self.focused_id(),
+            _ => {}
+        }
+        None
+    }
+
+    /// Tab and Shift+Tab move focus and Space activates the focused item,
+    /// independent of the key bindings. Returns the id of the activated item.
+    pub fn handle_key(&mut self, event: &crossterm::event::KeyEvent) -> Option<T> {
+        if event.kind == KeyEventKind::Release {
+            return None;
+        }
+        match event.code {
+            KeyCode::Tab => self.focus_next(),
+            KeyCode::BackTab => self.focus_prev(),
+            KeyCode::Char(' ') => return self.focused_id(),
+            _ => {}
+        }
+        None
+    }
+
+    /// Updates hover, pressed and focus state from a mouse event.
+    ///
+    /// Hovering an item also focuses it, so keyboard navigation continues from
+    /// wherever the mouse was. An item is activated by a click: pressing the left
+    /// button arms it and releasing over the same item fires it, releasing anywhere
+    /// else cancels the press. Returns the id of the item activated by this event.
+    pub fn handle_mouse(&mut self, event: &crossterm::event::MouseEvent) -> Option<T> {
+        let index = self.item_at(event.column, event.row);
+        match event.kind {
+            MouseEventKind::Moved | MouseEventKind::Drag(MouseButton::Left) => {
+                self.hovered = index;
+                self.focused = index.or(self.focused);
+                None
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.hovered = index;
+                self.pressed = index;
+                self.focused = index.or(self.focused);
+                None
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                self.hovered = index;
+                let pressed = self.pressed.take();
+                match (pressed, index) {
+                    (Some(pressed), Some(index)) if pressed == index => Some(self.items[index].id),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Draws the items of a [`MenuState`] as a centered column of buttons.
+pub struct Menu<'a, T> {
+    theme: &'a Scheme,
+    button_height: u16,
+    spacing: u16,
+    _items: PhantomData<T>,
+}
+
+impl<'a, T> Menu<'a, T> {
+    pub fn new(theme: &'a Scheme) -> Self {
+        Self {
+            theme,
+            button_height: 3,
+            spacing: 1,
+            _items: PhantomData,
+        }
+    }
+
+    /// Rows taken by each button, borders are only drawn from 3 rows up.
+    pub fn button_height(mut self, height: u16) -> Self {
+        self.button_height = height;
+        self
+    }
+
+    /// Empty rows between the buttons.
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Rows needed to draw `items` buttons without the margins.
+    pub fn height(&self, items: usize) -> u16 {
+        let items = items as u16;
+        items * self.button_height + items.saturating_sub(1) * self.spacing
+    }
+
+    fn item_style(&self, state: MenuItemState) -> Style {
+        match state {
+            MenuItemState::Normal => self.theme.button_style(),
+            MenuItemState::Hovered => self.theme.hover_style(),
+            MenuItemState::Pressed => self.theme.armed_style(),
+            MenuItemState::Focused => self.theme.focus(),
+            MenuItemState::Disabled => self.theme.disabled_style(),
+        }
+    }
+}
+
+impl<T: Copy + PartialEq> StatefulWidgetRef for Menu<'_, T> {
+    type State = MenuState<T>;
+
+    fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let items = state.items.len() as u16;
+        // too short for the buttons: a row per button, spaced if there is room
+        let (button_height, spacing) = if area.height >= self.height(state.items.len()) {
+            (self.button_height, self.spacing)
+        } else if area.height >= items + items.saturating_sub(1) * self.spacing {
+            (1, self.spacing)
+        } else {
+            (1, 0)
+        };
+        let used = items * button_height + items.saturating_sub(1) * spacing;
+        // margin at top and bottom for vertical centering, if there is room left
+        let margin = if area.height > used {
+            Constraint::Percentage(20)
+        } else {
+            Constraint::Length(0)
+        };
+        let mut constraints = vec![margin];
+        for index in 0..state.items.len() {
+            if index > 0 {
+                constraints.push(Constraint::Length(spacing));
+            }
+            constraints.push(Constraint::Length(button_height));
+        }
+        constraints.push(margin);
+        let chunks = Layout::vertical(constraints).split(area);
+
+        state.rects.clear();
+        for (index, item) in state.items.iter().enumerate() {
+            let rect = chunks[1 + index * 2];
+            let style = self.item_style(state.item_state(index));
+            let borders = if rect.height >= 3 {
+                Borders::ALL
+            } else {
+                Borders::NONE
+            };
+            Paragraph::new(item.label.as_str())
+                .block(Block::default().borders(borders).border_style(style))
+                .style(style)
+                .alignment(Alignment::Center)
+                .render_ref(rect, buf);
+            state.rects.push(rect);
+        }
+    }
+}
@@ -1,35 +1,53 @@
-use std::collections::HashMap;
-
-use bevy::{log, prelude::*};
-use bevy_ratatui::{
-    error::exit_on_error,
-    event::{KeyEvent, MouseEvent},
-    terminal::RatatuiContext,
-};
-use crossterm::event::{KeyEventKind, MouseEventKind};
+use bevy::prelude::*;
+use bevy_ratatui::event::{KeyEvent, MouseEvent};
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Constraint, Direction, Flex, Layout, Position},
-    style::{Color, Style},
-    widgets::{Block, Borders, Paragraph, StatefulWidgetRef, WidgetRef},
+    layout::{Constraint, Direction, Flex, Layout},
+    widgets::{block, Block, StatefulWidgetRef, WidgetRef},
 };
 
-use crate::states::{
-    app_state::{self, AppState},
-    options_state::OptionsState,
+use crate::{
+    config::{settings::Settings, theme::Theme},
+    mini_salsa::theme::Scheme,
+    input::{
+        actions::GameAction,
+        routing::{InputEvent, InputRoutingAppExt, InputRoutingSet},
+    },
+    states::{
+        app_state::AppState,
+        menu_screen::MenuScreen,
+        navigation::Navigate,
+        screen::Screen,
+    },
+    terminal::{context::TerminalContext, error::exit_on_error},
+    widgets::{
+        dialog::{Dialog, DialogClosed, Dialogs},
+        game::GameSession,
+        menu::{Menu, MenuActivated, MenuState},
+        video::apply_video_settings,
+    },
 };
 
+/// Id of the dialog asking before a paused game is dropped for a new one.
+pub const NEW_GAME_DIALOG: &str = "new_game";
+
 type Rect = ratatui::layout::Rect;
 
 pub struct OptionsPlugin;
 
 impl Plugin for OptionsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<OptionsEvent>()
-            .init_resource::<RegisteredComponents>()
-            .add_systems(PreUpdate, options_event_handler)
-            .add_systems(Update, render_options.pipe(exit_on_error))
-            .init_state::<OptionsState>();
+        app.route_input::<OptionsEvent>(Screen::Options)
+            .add_event::<MenuActivated<OptionComponents>>()
+            .insert_resource(options_menu())
+            .add_systems(OnEnter(Screen::Options), reset_options_menu)
+            .add_systems(
+                PreUpdate,
+                (options_event_handler, options_menu_activated, new_game_dialog_closed)
+                    .chain()
+                    .in_set(InputRoutingSet::Handle),
+            )
+            .add_systems(Update, render_options.pipe(exit_on_error));
     }
 }
 
@@ -37,9 +55,20 @@ impl Plugin for OptionsPlugin {
 pub enum OptionsEvent {
     MouseEvent(MouseEvent),
     KeyEvent(KeyEvent),
+    Action(GameAction),
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+impl From<InputEvent> for OptionsEvent {
+    fn from(event: InputEvent) -> Self {
+        match event {
+            InputEvent::Key(key_event) => OptionsEvent::KeyEvent(key_event),
+            InputEvent::Mouse(mouse_event) => OptionsEvent::MouseEvent(mouse_event),
+            InputEvent::Action(action) => OptionsEvent::Action(action),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub enum OptionComponents {
     NewGame,
     KeyBindings,
@@ -48,8 +77,23 @@ pub enum OptionComponents {
     Back,
 }
 
-impl StatefulWidgetRef for OptionsState {
-    type State = RegisteredComponents;
+/// The items of the Options menu, nothing hovered or focused yet.
+pub fn options_menu() -> MenuState<OptionComponents> {
+    MenuState::new([
+        (OptionComponents::NewGame, "New Game"),
+        (OptionComponents::KeyBindings, "Key Bindings"),
+        (OptionComponents::Video, "Video"),
+        (OptionComponents::Audio, "Audio"),
+        (OptionComponents::Back, "Back"),
+    ])
+}
+
+pub struct OptionsWidget<'a> {
+    pub theme: &'a Scheme,
+}
+
+impl StatefulWidgetRef for OptionsWidget<'_> {
+    type State = MenuState<OptionComponents>;
     fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let sub_area = Layout::default()
             .direction(Direction::Horizontal)
@@ -60,241 +104,102 @@ impl StatefulWidgetRef for OptionsState {
             ])
             .margin(4)
             .split(area);
-        Block::default()
+        Block::bordered()
+            .border_type(block::BorderType::Rounded)
+            .border_style(self.theme.block())
+            .title_style(self.theme.block_title())
+            .style(self.theme.data())
             .title("Options")
-            .borders(Borders::ALL)
             .render_ref(area, buf);
 
-        // Add margin at top and bottom for vertical centering
-        let vertical_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Percentage(20), // Top margin
-                Constraint::Length(3),      // New Game
-                Constraint::Length(1),      // Spacing
-                Constraint::Length(3),      // Key Bindings
-                Constraint::Length(1),      // Spacing
-                Constraint::Length(3),      // Video
-                Constraint::Length(1),      // Spacing
-                Constraint::Length(3),      // Audio
-                Constraint::Length(1),      // Spacing
-                Constraint::Length(3),      // Back
-                Constraint::Percentage(20), // Bottom margin
-            ])
-            .split(sub_area[0]);
-
-        // Render buttons with centered alignment
-        self.render_new_game_button(vertical_chunks[1], buf, state);
-        self.render_key_bindings_button(vertical_chunks[3], buf, state);
-        self.render_video_button(vertical_chunks[5], buf, state);
-        self.render_audio_button(vertical_chunks[7], buf, state);
-        self.render_back_button(vertical_chunks[9], buf, state);
+        Menu::new(self.theme).render_ref(sub_area[0], buf, state);
     }
 }
 
+fn reset_options_menu(mut menu: ResMut<MenuState<OptionComponents>>) {
+    menu.reset();
+}
+
 fn options_event_handler(
-    mut app_state: ResMut<NextState<AppState>>,
-    mut send_options_state: ResMut<NextState<OptionsState>>,
-    options_state: Res<State<OptionsState>>,
     mut options_events: EventReader<OptionsEvent>,
-    registered_components: Res<RegisteredComponents>,
-    context: Res<RatatuiContext>,
+    mut menu: ResMut<MenuState<OptionComponents>>,
+    mut activated: EventWriter<MenuActivated<OptionComponents>>,
 ) {
-    let state = options_state.get();
-    let size = context.size().unwrap();
     for event in options_events.read() {
-        match event {
-            OptionsEvent::MouseEvent(m_evt) => match m_evt.kind {
-                MouseEventKind::Moved => {
-                    // find the button that is hovered
-                    let x = m_evt.column;
-                    let y = m_evt.row;
-                    if registered_components.is_over(OptionComponents::KeyBindings, x, y) {
-                        send_options_state.set(OptionsState::KeyBindingsOver);
-                    } else if registered_components.is_over(OptionComponents::NewGame, x, y) {
-                        send_options_state.set(OptionsState::NewGameOver);                        
-                    } else if registered_components.is_over(OptionComponents::Video, x, y) {
-                        send_options_state.set(OptionsState::VideoOver);                        
-                    } else if registered_components.is_over(OptionComponents::Audio, x, y) {
-                        send_options_state.set(OptionsState::AudioOver);                        
-                    } else if registered_components.is_over(OptionComponents::Back, x, y) {
-                        send_options_state.set(OptionsState::BackOver);                       
-                    } else {
-                        send_options_state.set(OptionsState::None);                        
-                    }
-                    
-                }
-                MouseEventKind::Down(btn) => {
-                    // find the button that is hovered
-                    let x = m_evt.column;
-                    let y = m_evt.row;
-                    if registered_components.is_over(OptionComponents::KeyBindings, x, y) {
-                        send_options_state.set(OptionsState::KeyBindingsDown);
-                    } else if registered_components.is_over(OptionComponents::NewGame, x, y) {
-                        send_options_state.set(OptionsState::NewGameDown);                        
-                    } else if registered_components.is_over(OptionComponents::Video, x, y) {
-                        send_options_state.set(OptionsState::VideoDown);                        
-                    } else if registered_components.is_over(OptionComponents::Audio, x, y) {
-                        send_options_state.set(OptionsState::AudioDown);                        
-                    } else if registered_components.is_over(OptionComponents::Back, x, y) {
-                        send_options_state.set(OptionsState::BackDown);                       
-                    } else {
-                        send_options_state.set(OptionsState::None);                        
-                    }
-                }
-                MouseEventKind::Up(btn) => {
+        let id = match event {
+            OptionsEvent::MouseEvent(m_evt) => menu.handle_mouse(m_evt),
+            OptionsEvent::KeyEvent(key_event) => menu.handle_key(key_event),
+            OptionsEvent::Action(action) => menu.handle_action(*action),
+        };
+        if let Some(id) = id {
+            activated.send(MenuActivated(id));
+        }
+    }
+}
 
-                },
-                _ => {
-                    info!("Some other mouse event")
-                }
-            },
-            OptionsEvent::KeyEvent(event) => {
-                match event.kind {
-                    KeyEventKind::Release => match event.code {
-                        crossterm::event::KeyCode::Esc => {
-                            app_state.set(AppState::Home);
-                        }
-                        _ => {}
-                    },
-                    _ => {}
-                }
+fn options_menu_activated(
+    app_state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut navigate: EventWriter<Navigate>,
+    mut dialogs: ResMut<Dialogs>,
+    mut activated: EventReader<MenuActivated<OptionComponents>>,
+) {
+    for MenuActivated(id) in activated.read() {
+        match id {
+            OptionComponents::NewGame if *app_state.get() == AppState::Paused => {
+                dialogs.open(Dialog::confirm(
+                    NEW_GAME_DIALOG,
+                    "New game",
+                    "Start a new game?\nThe game in progress is lost.",
+                ));
+            }
+            OptionComponents::NewGame => next_state.set(AppState::InGame),
+            OptionComponents::KeyBindings => {
+                navigate.send(Navigate::Push(MenuScreen::KeyBindings));
+            }
+            OptionComponents::Video => {
+                navigate.send(Navigate::Push(MenuScreen::Video));
+            }
+            OptionComponents::Audio => {
+                navigate.send(Navigate::Push(MenuScreen::Audio));
+            }
+            OptionComponents::Back => {
+                navigate.send(Navigate::Pop);
             }
         }
     }
 }
 
-
-#[derive(Debug, Clone, Eq, PartialEq, Resource, Default)]
-pub struct RegisteredComponents(HashMap<OptionComponents, Rect>);
-
-
-impl RegisteredComponents {
-    pub fn is_over(&self, component: OptionComponents, x: u16, y: u16) -> bool {
-        if let Some(rect) = self.0.get(&component) {
-            rect.contains(Position { x, y })
-        } else {
-            false
+/// Going from paused back in game keeps `Gameplay`, so the session is
+/// replaced here instead of by `OnEnter(Gameplay)`.
+fn new_game_dialog_closed(
+    mut commands: Commands,
+    mut app_state: ResMut<NextState<AppState>>,
+    mut closed: EventReader<DialogClosed>,
+) {
+    for dialog in closed.read() {
+        if dialog.id == NEW_GAME_DIALOG && dialog.is_confirmed() {
+            commands.insert_resource(GameSession::default());
+            app_state.set(AppState::InGame);
         }
     }
 }
 
 fn render_options(
-    options_state: ResMut<State<OptionsState>>,
-    app_state: Res<State<AppState>>,
-    mut context: ResMut<RatatuiContext>,
-    mut registered_components: ResMut<RegisteredComponents>,
+    screen: Res<State<Screen>>,
+    mut context: ResMut<TerminalContext>,
+    mut menu: ResMut<MenuState<OptionComponents>>,
+    settings: Res<Settings>,
+    theme: Res<Theme>,
 ) -> color_eyre::Result<()> {
-    let app_state = app_state.get();
-    if app_state != &AppState::Options {
+    if screen.get() != &Screen::Options {
         return Ok(());
     }
     context.draw(|frame| {
         let area = frame.area();
-        frame.render_stateful_widget_ref(*options_state.get(), area, &mut registered_components );
+        frame.render_stateful_widget_ref(OptionsWidget { theme: &theme }, area, &mut menu);
+        apply_video_settings(&settings.video, frame.buffer_mut());
     })?;
 
     Ok(())
 }
-
-impl OptionsState {
-    fn render_new_game_button(&self, area: Rect, buf: &mut Buffer, state: &mut RegisteredComponents) {
-        let (title, style) = match self {
-            OptionsState::NewGameOver => (
-                "New Game",
-                Style::default().fg(Color::Black).bg(Color::White),
-            ),
-            OptionsState::NewGameDown => (
-                "New Game",
-                Style::default().fg(Color::White).bg(Color::Black),
-            ),
-            _ => (
-                "New Game",
-                Style::default().fg(Color::White).bg(Color::Black),
-            ),
-        };
-        Paragraph::new(title)
-            .block(
-                Block::default()
-                .borders(Borders::ALL)
-                .border_style(style))
-            .style(style)
-            .alignment(Alignment::Center)
-            .render_ref(area, buf);
-
-        state.0.insert(OptionComponents::NewGame, area);
-    }
-
-    fn render_key_bindings_button(&self, area: Rect, buf: &mut Buffer, state: &mut RegisteredComponents) {
-        let (title, style) = match self {
-            OptionsState::KeyBindingsOver => {
-                ("Key Bindings", Style::default().fg(Color::Black).bg(Color::White))
-            }
-            OptionsState::KeyBindingsDown => {
-                ("Key Bindings", Style::default().fg(Color::White).bg(Color::Black))
-            }
-            _ => ("Key Bindings", Style::default().fg(Color::White).bg(Color::Black)),
-        };
-        Paragraph::new(title).block(Block::default().borders(Borders::ALL))
-            .style(style)
-            .alignment(Alignment::Center)
-            .render_ref(area, buf);
-
-        state.0.insert(OptionComponents::KeyBindings, area);
-    }
-
-    fn render_video_button(&self, area: Rect, buf: &mut Buffer, state: &mut RegisteredComponents) {
-        let (title, style) = match self {
-            OptionsState::VideoOver => {
-                ("Video", Style::default().fg(Color::Black).bg(Color::White))
-            }
-            OptionsState::VideoDown => {
-                ("Video", Style::default().fg(Color::White).bg(Color::Black))
-            }
-            _ => ("Video", Style::default().fg(Color::White).bg(Color::Black)),
-        };
-        Paragraph::new(title).block(Block::default().borders(Borders::ALL))
-            .style(style)
-            .alignment(Alignment::Center)
-            .render_ref(area, buf);
-
-
-        state.0.insert(OptionComponents::Video, area);
-    }
-
-    fn render_audio_button(&self, area: Rect, buf: &mut Buffer, state:  &mut RegisteredComponents) {
-        let (title, style) = match self {
-            OptionsState::AudioOver => {
-                ("Audio", Style::default().fg(Color::Black).bg(Color::White))
-            }
-            OptionsState::AudioDown => {
-                ("Audio", Style::default().fg(Color::White).bg(Color::Black))
-            }
-            _ => ("Audio", Style::default().fg(Color::White).bg(Color::Black)),
-        };
-        Paragraph::new(title).block(Block::default().borders(Borders::ALL))
-            .style(style)
-            .alignment(Alignment::Center)
-            .render_ref(area, buf);
-
-        state.0.insert(OptionComponents::Audio, area);
-    }
-
-    fn render_back_button(&self, area: Rect, buf: &mut Buffer, state:  &mut RegisteredComponents) {
-        let (title, style) = match self {
-            OptionsState::BackOver => {
-                ("Back", Style::default().fg(Color::Black).bg(Color::White))
-            }
-            OptionsState::BackDown => {
-                ("Back", Style::default().fg(Color::White).bg(Color::Black))
-            }
-            _ => ("Back", Style::default().fg(Color::White).bg(Color::Black)),
-        };
-        Paragraph::new(title).block(Block::default().borders(Borders::ALL))
-            .style(style)
-            .alignment(Alignment::Center)
-            .render_ref(area, buf);
-
-        state.0.insert(OptionComponents::Back, area);
-    }
-}
@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use bevy_ratatui::event::{KeyEvent, MouseEvent};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Flex, Layout, Rect},
+    widgets::{block, Block, StatefulWidgetRef, WidgetRef},
+};
+
+use crate::{
+    config::{settings::Settings, theme::Theme},
+    input::{
+        actions::GameAction,
+        routing::{InputEvent, InputRoutingAppExt, InputRoutingSet},
+    },
+    mini_salsa::theme::Scheme,
+    states::{
+        app_state::AppState,
+        menu_screen::MenuScreen,
+        navigation::Navigate,
+        screen::Screen,
+    },
+    terminal::{context::TerminalContext, error::exit_on_error},
+    widgets::{
+        dialog::{Dialog, DialogClosed, Dialogs},
+        menu::{Menu, MenuActivated, MenuState},
+        video::apply_video_settings,
+    },
+};
+
+/// Id of the dialog asking before the game is dropped for the main menu.
+pub const LEAVE_GAME_DIALOG: &str = "leave_game";
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.route_input::<PauseEvent>(Screen::Pause)
+            .add_event::<MenuActivated<PauseComponents>>()
+            .insert_resource(pause_menu())
+            .add_systems(OnEnter(Screen::Pause), reset_pause_menu)
+            .add_systems(
+                PreUpdate,
+                (pause_events_handler, pause_menu_activated, leave_game_dialog_closed)
+                    .chain()
+                    .in_set(InputRoutingSet::Handle),
+            )
+            .add_systems(Update, render_pause.pipe(exit_on_error));
+    }
+}
+
+#[derive(Debug, Clone, Event, PartialEq, Eq)]
+pub enum PauseEvent {
+    MouseEvent(MouseEvent),
+    KeyEvent(KeyEvent),
+    Action(GameAction),
+}
+
+impl From<InputEvent> for PauseEvent {
+    fn from(event: InputEvent) -> Self {
+        match event {
+            InputEvent::Key(key_event) => PauseEvent::KeyEvent(key_event),
+            InputEvent::Mouse(mouse_event) => PauseEvent::MouseEvent(mouse_event),
+            InputEvent::Action(action) => PauseEvent::Action(action),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum PauseComponents {
+    Resume,
+    Options,
+    MainMenu,
+}
+
+/// The items of the pause menu, nothing hovered or focused yet.
+pub fn pause_menu() -> MenuState<PauseComponents> {
+    MenuState::new([
+        (PauseComponents::Resume, "Resume"),
+        (PauseComponents::Options, "Options"),
+        (PauseComponents::MainMenu, "Main Menu"),
+    ])
+}
+
+pub struct PauseWidget<'a> {
+    pub theme: &'a Scheme,
+}
+
+impl StatefulWidgetRef for PauseWidget<'_> {
+    type State = MenuState<PauseComponents>;
+
+    fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let block = Block::bordered()
+            .border_type(block::BorderType::Rounded)
+            .border_style(self.theme.block())
+            .title_style(self.theme.block_title())
+            .style(self.theme.data())
+            .title("Paused");
+        let inner = block.inner(area);
+        block.render_ref(area, buf);
+
+        let menu = Menu::new(self.theme).spacing(0);
+        let [menu_area] = Layout::vertical([Constraint::Length(menu.height(state.items.len()))])
+            .flex(Flex::Center)
+            .areas(inner);
+        let [menu_area] = Layout::horizontal([Constraint::Length(25)])
+            .flex(Flex::Center)
+            .areas(menu_area);
+        menu.render_ref(menu_area, buf, state);
+    }
+}
+
+fn reset_pause_menu(mut menu: ResMut<MenuState<PauseComponents>>) {
+    menu.reset();
+}
+
+fn pause_events_handler(
+    mut app_state: ResMut<NextState<AppState>>,
+    mut events: EventReader<PauseEvent>,
+    mut menu: ResMut<MenuState<PauseComponents>>,
+    mut activated: EventWriter<MenuActivated<PauseComponents>>,
+) {
+    for event in events.read() {
+        let id = match event {
+            PauseEvent::MouseEvent(mouse_event) => menu.handle_mouse(mouse_event),
+            PauseEvent::KeyEvent(key_event) => menu.handle_key(key_event),
+            PauseEvent::Action(GameAction::Back) => {
+                app_state.set(AppState::InGame);
+                None
+            }
+            PauseEvent::Action(action) => menu.handle_action(*action),
+        };
+        if let Some(id) = id {
+            activated.send(MenuActivated(id));
+        }
+    }
+}
+
+fn pause_menu_activated(
+    mut app_state: ResMut<NextState<AppState>>,
+    mut navigate: EventWriter<Navigate>,
+    mut dialogs: ResMut<Dialogs>,
+    mut activated: EventReader<MenuActivated<PauseComponents>>,
+) {
+    for MenuActivated(id) in activated.read() {
+        match id {
+            PauseComponents::Resume => app_state.set(AppState::InGame),
+            PauseComponents::Options => {
+                navigate.send(Navigate::Push(MenuScreen::Options));
+            }
+            PauseComponents::MainMenu => dialogs.open(Dialog::confirm(
+                LEAVE_GAME_DIALOG,
+                "Leave game",
+                "Return to the main menu?\nThe game in progress is lost.",
+            )),
+        }
+    }
+}
+
+fn leave_game_dialog_closed(
+    mut app_state: ResMut<NextState<AppState>>,
+    mut closed: EventReader<DialogClosed>,
+) {
+    for dialog in closed.read() {
+        if dialog.id == LEAVE_GAME_DIALOG && dialog.is_confirmed() {
+            app_state.set(AppState::MainMenu);
+        }
+    }
+}
+
+fn render_pause(
+    screen: Res<State<Screen>>,
+    mut context: ResMut<TerminalContext>,
+    mut menu: ResMut<MenuState<PauseComponents>>,
+    settings: Res<Settings>,
+    theme: Res<Theme>,
+) -> color_eyre::Result<()> {
+    if screen.get() != &Screen::Pause {
+        return Ok(());
+    }
+    context.draw(|frame| {
+        let area = frame.area();
+        frame.render_stateful_widget_ref(PauseWidget { theme: &theme }, area, &mut menu);
+        apply_video_settings(&settings.video, frame.buffer_mut());
+    })?;
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::Span,
+    widgets::{Clear, Widget, WidgetRef},
+};
+
+use crate::{
+    config::{key_bindings::format_key_sequence, settings::Settings, theme::Theme},
+    input::actions::PendingSequence,
+    mini_salsa::theme::Scheme,
+    terminal::{
+        context::{FrameSet, TerminalContext},
+        error::exit_on_error,
+    },
+    widgets::{
+        dialog::render_dialog,
+        key_bindings::KeySequence,
+        video::apply_video_settings,
+    },
+};
+
+/// Shows the chords of a started key sequence until it is completed or dropped.
+pub struct PendingSequencePlugin;
+
+impl Plugin for PendingSequencePlugin {
+    fn build(&self, app: &mut App) {
+        // over the screen and its dialog, in the same frame
+        app.add_systems(
+            PostUpdate,
+            render_pending_sequence
+                .pipe(exit_on_error)
+                .in_set(FrameSet::Overlay)
+                .after(render_dialog),
+        );
+    }
+}
+
+/// The typed chords followed by an ellipsis, in the bottom right corner.
+pub struct PendingSequenceWidget<'a> {
+    pub sequence: &'a KeySequence,
+    pub theme: &'a Scheme,
+}
+
+impl WidgetRef for PendingSequenceWidget<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let text = format!(" {} … ", format_key_sequence(self.sequence));
+        let width = (text.chars().count() as u16).min(area.width);
+        if width == 0 || area.height == 0 {
+            return;
+        }
+        let indicator = Rect {
+            x: area.right() - width,
+            y: area.bottom() - 1,
+            width,
+            height: 1,
+        };
+        Clear.render(indicator, buf);
+        Span::styled(text, self.theme.focus()).render(indicator, buf);
+    }
+}
+
+fn render_pending_sequence(
+    mut context: ResMut<TerminalContext>,
+    pending: Res<PendingSequence>,
+    settings: Res<Settings>,
+    theme: Res<Theme>,
+) -> color_eyre::Result<()> {
+    let Some(sequence) = pending.sequence() else {
+        return Ok(());
+    };
+    context.draw(|frame| {
+        let area = frame.area();
+        PendingSequenceWidget {
+            sequence,
+            theme: &theme,
+        }
+        .render_ref(area, frame.buffer_mut());
+        apply_video_settings(&settings.video, frame.buffer_mut());
+    })?;
+    Ok(())
+}
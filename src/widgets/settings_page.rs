@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+
+use crate::{
+    config::settings::{detect_settings_changes, Settings, SettingsSection},
+    input::routing::InputRoutingSet,
+    states::{
+        navigation::{apply_navigation, Navigate, UnsavedChanges},
+        screen::Screen,
+    },
+    widgets::dialog::{Dialog, DialogClosed, Dialogs},
+};
+
+/// Id of the dialog asking before a page with changes is left.
+pub const DISCARD_CHANGES_DIALOG: &str = "discard_changes";
+
+/// The copy of the [`Settings`] the Video, Audio and Key Bindings pages edit.
+///
+/// Taken when one of them is entered. [`SettingsCommand::Apply`] copies the
+/// sections of the page back, which is also when the settings are saved.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Resource, Deref, DerefMut)]
+pub struct SettingsDraft(pub Settings);
+
+impl SettingsDraft {
+    /// Whether the draft has changes to `sections` that were not applied.
+    pub fn is_dirty(&self, settings: &Settings, sections: &[SettingsSection]) -> bool {
+        settings
+            .changed_sections(&self.0)
+            .iter()
+            .any(|section| sections.contains(section))
+    }
+}
+
+/// Sent by the settings pages, resetting a page is left to the page itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub enum SettingsCommand {
+    /// Commits the draft.
+    Apply,
+    /// Leaves the page, asking first if that drops changes.
+    Cancel,
+}
+
+/// The sections of the [`SettingsDraft`] the screen edits, none if it is not a settings page.
+pub fn page_sections(screen: &Screen) -> &'static [SettingsSection] {
+    match screen {
+        Screen::Video => &[SettingsSection::Video, SettingsSection::Theme],
+        Screen::Audio => &[SettingsSection::Audio],
+        Screen::KeyBindings => &[SettingsSection::Controls],
+        _ => &[],
+    }
+}
+
+/// Keeps the draft of the settings pages and carries out their [`SettingsCommand`]s.
+pub struct SettingsPagePlugin;
+
+impl Plugin for SettingsPagePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SettingsDraft>()
+            .add_event::<SettingsCommand>()
+            .add_systems(OnEnter(Screen::Video), begin_draft)
+            .add_systems(OnEnter(Screen::Audio), begin_draft)
+            .add_systems(OnEnter(Screen::KeyBindings), begin_draft)
+            .add_systems(
+                PreUpdate,
+                (
+                    settings_commands_handler,
+                    discard_changes_dialog_closed,
+                    track_unsaved_changes,
+                )
+                    .chain()
+                    .after(InputRoutingSet::Handle)
+                    .before(apply_navigation)
+                    .before(detect_settings_changes),
+            );
+    }
+}
+
+fn begin_draft(settings: Res<Settings>, mut draft: ResMut<SettingsDraft>) {
+    draft.0 = settings.clone();
+}
+
+fn settings_commands_handler(
+    screen: Res<State<Screen>>,
+    mut commands: EventReader<SettingsCommand>,
+    mut settings: ResMut<Settings>,
+    draft: Res<SettingsDraft>,
+    mut dialogs: ResMut<Dialogs>,
+    mut navigate: EventWriter<Navigate>,
+) {
+    let sections = page_sections(screen.get());
+    for command in commands.read() {
+        match command {
+            SettingsCommand::Apply => {
+                // other sections may have changed since the draft was taken
+                let mut next = settings.clone();
+                next.copy_sections(&draft, sections);
+                settings.set_if_neq(next);
+            }
+            SettingsCommand::Cancel if draft.is_dirty(&settings, sections) => {
+                dialogs.open(Dialog::confirm(
+                    DISCARD_CHANGES_DIALOG,
+                    "Discard changes",
+                    "Leave without applying your changes?",
+                ));
+            }
+            SettingsCommand::Cancel => {
+                navigate.send(Navigate::Pop);
+            }
+        }
+    }
+}
+
+fn discard_changes_dialog_closed(
+    mut closed: EventReader<DialogClosed>,
+    settings: Res<Settings>,
+    mut draft: ResMut<SettingsDraft>,
+    mut navigate: EventWriter<Navigate>,
+) {
+    for dialog in closed.read() {
+        if dialog.id == DISCARD_CHANGES_DIALOG && dialog.is_confirmed() {
+            draft.0 = settings.clone();
+            navigate.send(Navigate::Pop);
+        }
+    }
+}
+
+fn track_unsaved_changes(
+    screen: Res<State<Screen>>,
+    settings: Res<Settings>,
+    draft: Res<SettingsDraft>,
+    mut unsaved: ResMut<UnsavedChanges>,
+) {
+    let dirty = draft.is_dirty(&settings, page_sections(screen.get()));
+    unsaved.set_if_neq(UnsavedChanges(dirty));
+}
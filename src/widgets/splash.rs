@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ratatui::event::{KeyEvent, MouseEvent};
+use crossterm::event::{KeyEventKind, MouseEventKind};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    text::Text,
+    widgets::{block, Block, Paragraph, Widget, WidgetRef},
+};
+
+use crate::{
+    config::{settings::Settings, theme::Theme},
+    input::{
+        actions::GameAction,
+        routing::{InputEvent, InputRoutingAppExt, InputRoutingSet},
+    },
+    mini_salsa::theme::Scheme,
+    states::{app_state::AppState, screen::Screen},
+    terminal::{context::TerminalContext, error::exit_on_error},
+    widgets::{home::TitleArt, video::apply_video_settings},
+};
+
+/// How long the splash screen stays up unless a key is pressed.
+#[derive(Debug, Clone, Copy, Resource)]
+pub struct SplashDuration(pub Duration);
+
+impl Default for SplashDuration {
+    fn default() -> Self {
+        Self(Duration::from_secs(2))
+    }
+}
+
+#[derive(Debug, Resource)]
+struct SplashTimer(Timer);
+
+pub struct SplashPlugin;
+
+impl Plugin for SplashPlugin {
+    fn build(&self, app: &mut App) {
+        app.route_input::<SplashEvent>(Screen::Splash)
+            .init_resource::<SplashDuration>()
+            .add_systems(OnEnter(Screen::Splash), start_splash_timer)
+            .add_systems(PreUpdate, splash_events_handler.in_set(InputRoutingSet::Handle))
+            .add_systems(Update, tick_splash_timer.run_if(in_state(Screen::Splash)))
+            .add_systems(Update, render_splash.pipe(exit_on_error));
+    }
+}
+
+#[derive(Debug, Clone, Event, PartialEq, Eq)]
+pub enum SplashEvent {
+    MouseEvent(MouseEvent),
+    KeyEvent(KeyEvent),
+    Action(GameAction),
+}
+
+impl From<InputEvent> for SplashEvent {
+    fn from(event: InputEvent) -> Self {
+        match event {
+            InputEvent::Key(key_event) => SplashEvent::KeyEvent(key_event),
+            InputEvent::Mouse(mouse_event) => SplashEvent::MouseEvent(mouse_event),
+            InputEvent::Action(action) => SplashEvent::Action(action),
+        }
+    }
+}
+
+pub struct SplashWidget<'a> {
+    pub theme: &'a Scheme,
+    pub title: &'a str,
+    /// Shown instead of `title` when the art doesn't fit.
+    pub title_text: &'a str,
+}
+
+impl WidgetRef for SplashWidget<'_> {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .border_type(block::BorderType::Rounded)
+            .border_style(self.theme.block())
+            .style(self.theme.data());
+        let inner = block.inner(area);
+        block.render_ref(area, buf);
+
+        let art = Text::from(self.title);
+        let title = if art.width() as u16 <= inner.width {
+            art
+        } else {
+            Text::from(self.title_text)
+        };
+        let [title_area, hint_area] = Layout::vertical([
+            Constraint::Length(title.height() as u16 + 1),
+            Constraint::Length(1),
+        ])
+        .flex(Flex::Center)
+        .areas(inner);
+
+        let [title_area] = Layout::horizontal([Constraint::Length(title.width() as u16)])
+            .flex(Flex::Center)
+            .areas(title_area);
+        Paragraph::new(title)
+            .style(self.theme.block_title())
+            .render(title_area, buf);
+        Paragraph::new("Press any key")
+            .style(self.theme.data())
+            .alignment(Alignment::Center)
+            .render(hint_area, buf);
+    }
+}
+
+fn start_splash_timer(mut commands: Commands, duration: Res<SplashDuration>) {
+    commands.insert_resource(SplashTimer(Timer::new(duration.0, TimerMode::Once)));
+}
+
+fn tick_splash_timer(
+    time: Res<Time>,
+    mut timer: ResMut<SplashTimer>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    if timer.0.tick(time.delta()).finished() {
+        app_state.set(AppState::MainMenu);
+    }
+}
+
+/// Any key or click skips the splash screen.
+fn splash_events_handler(
+    mut app_state: ResMut<NextState<AppState>>,
+    mut events: EventReader<SplashEvent>,
+) {
+    for event in events.read() {
+        let skip = match event {
+            SplashEvent::KeyEvent(key_event) => key_event.kind == KeyEventKind::Press,
+            SplashEvent::MouseEvent(mouse_event) => {
+                matches!(mouse_event.kind, MouseEventKind::Down(_))
+            }
+            SplashEvent::Action(_) => false,
+        };
+        if skip {
+            app_state.set(AppState::MainMenu);
+        }
+    }
+}
+
+fn render_splash(
+    screen: Res<State<Screen>>,
+    mut context: ResMut<TerminalContext>,
+    settings: Res<Settings>,
+    title_art: Res<TitleArt>,
+    theme: Res<Theme>,
+) -> color_eyre::Result<()> {
+    if screen.get() != &Screen::Splash {
+        return Ok(());
+    }
+    context.draw(|frame| {
+        let area = frame.area();
+        let widget = SplashWidget {
+            theme: &theme,
+            title: title_art.for_border_set(settings.video.border_set),
+            title_text: &title_art.text,
+        };
+        frame.render_widget_ref(widget, area);
+        apply_video_settings(&settings.video, frame.buffer_mut());
+    })?;
+    Ok(())
+}
@@ -0,0 +1,390 @@
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy_ratatui::event::{KeyEvent, MouseEvent};
+use crossterm::{
+    cursor::{DisableBlinking, EnableBlinking},
+    event::{MouseButton, MouseEventKind},
+};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Flex, Layout, Position, Rect},
+    text::Span,
+    widgets::{block, Block, Paragraph, StatefulWidgetRef, Widget, WidgetRef},
+};
+
+use crate::{
+    config::{
+        theme::{Theme, ThemeRegistry, ThemeSettings},
+        settings::{settings_changed_any, Settings, SettingsSection},
+        video::{BorderSet, CursorShape, VideoSettings, FRAME_RATES},
+    },
+    input::{
+        actions::GameAction,
+        routing::{InputEvent, InputRoutingAppExt, InputRoutingSet},
+    },
+    mini_salsa::{color_depth::ColorDepth, theme::Scheme},
+    states::screen::Screen,
+    terminal::{context::TerminalContext, error::exit_on_error},
+    widgets::settings_page::{SettingsCommand, SettingsDraft},
+};
+
+pub struct VideoPlugin;
+
+impl Plugin for VideoPlugin {
+    fn build(&self, app: &mut App) {
+        app.route_input::<VideoEvent>(Screen::Video)
+            .init_resource::<VideoScreenState>()
+            .init_resource::<FrameRateLimit>()
+            .add_systems(OnEnter(Screen::Video), reset_video_screen_state)
+            .add_systems(PreUpdate, video_events_handler.in_set(InputRoutingSet::Handle))
+            .add_systems(Update, render_video.pipe(exit_on_error))
+            .add_systems(
+                Update,
+                apply_cursor_style.pipe(exit_on_error).run_if(settings_changed_any(&[
+                    SettingsSection::Video,
+                    SettingsSection::Accessibility,
+                ])),
+            )
+            .add_systems(
+                Last,
+                limit_frame_rate.run_if(resource_equals(FrameRateLimit(true))),
+            );
+    }
+}
+
+/// Whether frames wait out the frame time of the video settings, on by default.
+///
+/// Turned off where frames should run back to back, such as in tests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource)]
+pub struct FrameRateLimit(pub bool);
+
+impl Default for FrameRateLimit {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+#[derive(Debug, Clone, Event, PartialEq, Eq)]
+pub enum VideoEvent {
+    MouseEvent(MouseEvent),
+    KeyEvent(KeyEvent),
+    Action(GameAction),
+}
+
+impl From<InputEvent> for VideoEvent {
+    fn from(event: InputEvent) -> Self {
+        match event {
+            InputEvent::Key(key_event) => VideoEvent::KeyEvent(key_event),
+            InputEvent::Mouse(mouse_event) => VideoEvent::MouseEvent(mouse_event),
+            InputEvent::Action(action) => VideoEvent::Action(action),
+        }
+    }
+}
+
+/// A row on the Video screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VideoOption {
+    FrameRate,
+    ColorDepth,
+    BorderSet,
+    CursorShape,
+    CursorBlink,
+    Theme,
+    Apply,
+    Reset,
+    Back,
+}
+
+impl VideoOption {
+    pub const ALL: [VideoOption; 9] = [
+        VideoOption::FrameRate,
+        VideoOption::ColorDepth,
+        VideoOption::BorderSet,
+        VideoOption::CursorShape,
+        VideoOption::CursorBlink,
+        VideoOption::Theme,
+        VideoOption::Apply,
+        VideoOption::Reset,
+        VideoOption::Back,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VideoOption::FrameRate => "Frame rate",
+            VideoOption::ColorDepth => "Color depth",
+            VideoOption::BorderSet => "Borders",
+            VideoOption::CursorShape => "Cursor",
+            VideoOption::CursorBlink => "Cursor blink",
+            VideoOption::Theme => "Colors",
+            VideoOption::Apply => "Apply",
+            VideoOption::Reset => "Reset to defaults",
+            VideoOption::Back => "Back",
+        }
+    }
+
+    /// Rows that act on the whole page instead of holding a setting.
+    pub fn is_command(&self) -> bool {
+        matches!(self, VideoOption::Apply | VideoOption::Reset | VideoOption::Back)
+    }
+
+    pub fn value(&self, settings: &Settings) -> String {
+        let video = &settings.video;
+        match self {
+            VideoOption::FrameRate => format!("{} fps", video.frame_rate),
+            VideoOption::ColorDepth => video.color_depth.label().to_string(),
+            VideoOption::BorderSet => video.border_set.label().to_string(),
+            VideoOption::CursorShape => video.cursor_shape.label().to_string(),
+            VideoOption::CursorBlink => if video.cursor_blink { "On" } else { "Off" }.to_string(),
+            VideoOption::Theme => settings.theme.name.clone(),
+            VideoOption::Apply | VideoOption::Reset | VideoOption::Back => String::new(),
+        }
+    }
+
+    /// Steps the setting of this row to its next or previous value.
+    ///
+    /// Color schemes are picked from the `schemes` that can be selected.
+    pub fn cycle(&self, settings: &mut Settings, schemes: &[String], forward: bool) {
+        let video = &mut settings.video;
+        match self {
+            VideoOption::FrameRate => {
+                video.frame_rate = cycle(&FRAME_RATES, &video.frame_rate, forward)
+            }
+            VideoOption::ColorDepth => {
+                video.color_depth = cycle(&ColorDepth::ALL, &video.color_depth, forward)
+            }
+            VideoOption::BorderSet => {
+                video.border_set = cycle(&BorderSet::ALL, &video.border_set, forward)
+            }
+            VideoOption::CursorShape => {
+                video.cursor_shape = cycle(&CursorShape::ALL, &video.cursor_shape, forward)
+            }
+            VideoOption::CursorBlink => video.cursor_blink = !video.cursor_blink,
+            VideoOption::Theme => {
+                if !schemes.is_empty() {
+                    settings.theme.name = cycle(schemes, &settings.theme.name, forward)
+                }
+            }
+            VideoOption::Apply | VideoOption::Back => {}
+            VideoOption::Reset => {
+                *video = VideoSettings::default();
+                settings.theme = ThemeSettings::default();
+            }
+        }
+    }
+}
+
+fn cycle<T: Clone + PartialEq>(values: &[T], current: &T, forward: bool) -> T {
+    let index = values.iter().position(|v| v == current).unwrap_or(0);
+    let next = if forward {
+        (index + 1) % values.len()
+    } else {
+        (index + values.len() - 1) % values.len()
+    };
+    values[next].clone()
+}
+
+/// Selection and hit areas of the Video screen.
+#[derive(Debug, Default, Clone, Resource)]
+pub struct VideoScreenState {
+    pub selected: usize,
+    rows: Vec<(VideoOption, Rect)>,
+}
+
+impl VideoScreenState {
+    pub fn selected_option(&self) -> VideoOption {
+        VideoOption::ALL[self.selected.min(VideoOption::ALL.len() - 1)]
+    }
+
+    fn option_at(&self, x: u16, y: u16) -> Option<(usize, VideoOption)> {
+        self.rows
+            .iter()
+            .position(|(_, rect)| rect.contains(Position { x, y }))
+            .map(|index| (index, self.rows[index].0))
+    }
+}
+
+pub struct VideoWidget<'a> {
+    pub settings: &'a Settings,
+    pub theme: &'a Scheme,
+}
+
+impl StatefulWidgetRef for VideoWidget<'_> {
+    type State = VideoScreenState;
+
+    fn render_ref(&self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        Block::bordered()
+            .border_type(block::BorderType::Rounded)
+            .border_style(self.theme.block())
+            .title_style(self.theme.block_title())
+            .style(self.theme.data())
+            .title("Video")
+            .render_ref(area, buf);
+
+        let [column] = Layout::horizontal([Constraint::Length(44)])
+            .flex(Flex::Center)
+            .areas(area.inner(ratatui::layout::Margin::new(2, 2)));
+        let rows = Layout::vertical(VideoOption::ALL.map(|_| Constraint::Length(2)))
+            .flex(Flex::Center)
+            .split(column);
+
+        state.rows.clear();
+        for (index, (option, row)) in VideoOption::ALL.iter().zip(rows.iter()).enumerate() {
+            let row = Rect { height: 1, ..*row };
+            let style = if index == state.selected {
+                self.theme.focus()
+            } else {
+                self.theme.data()
+            };
+            let [label_area, value_area] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(20)]).areas(row);
+            buf.set_style(row, style);
+            Span::from(format!(" {}", option.label())).render(label_area, buf);
+            if !option.is_command() {
+                Paragraph::new(format!("< {} >", option.value(self.settings)))
+                    .alignment(Alignment::Right)
+                    .render(value_area, buf);
+            }
+            state.rows.push((*option, row));
+        }
+    }
+}
+
+/// Applies the settings that change how the whole frame looks.
+///
+/// Every screen calls this on its buffer after rendering. The color depth is
+/// already applied to the [`Theme`] the widgets draw with.
+pub fn apply_video_settings(settings: &VideoSettings, buf: &mut Buffer) {
+    if settings.border_set == BorderSet::Ascii {
+        for cell in buf.content.iter_mut() {
+            if let Some(ascii) = ascii_symbol(cell.symbol()) {
+                cell.set_symbol(ascii);
+            }
+        }
+    }
+}
+
+/// ASCII stand-ins for the line drawing and scrollbar symbols used by the widgets.
+fn ascii_symbol(symbol: &str) -> Option<&'static str> {
+    let ascii = match symbol {
+        "─" | "━" | "═" | "╌" => "-",
+        "│" | "┃" | "║" | "╎" => "|",
+        "┌" | "┐" | "└" | "┘" | "╭" | "╮" | "╰" | "╯" | "┏" | "┓" | "┗" | "┛" | "╔" | "╗"
+        | "╚" | "╝" | "├" | "┤" | "┬" | "┴" | "┼" => "+",
+        "▲" | "↑" => "^",
+        "▼" | "↓" => "v",
+        "◄" | "←" => "<",
+        "►" | "→" => ">",
+        "█" => "#",
+        "░" | "▒" | "▓" => ":",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+fn reset_video_screen_state(mut state: ResMut<VideoScreenState>) {
+    state.selected = 0;
+}
+
+/// Edits the [`SettingsDraft`], the settings only change on Apply.
+fn video_events_handler(
+    mut commands: EventWriter<SettingsCommand>,
+    mut events: EventReader<VideoEvent>,
+    mut state: ResMut<VideoScreenState>,
+    mut draft: ResMut<SettingsDraft>,
+    registry: Res<ThemeRegistry>,
+) {
+    let last = VideoOption::ALL.len() - 1;
+    for event in events.read() {
+        let (option, forward) = match event {
+            VideoEvent::Action(GameAction::Up) => {
+                state.selected = state.selected.saturating_sub(1);
+                continue;
+            }
+            VideoEvent::Action(GameAction::Down) => {
+                state.selected = (state.selected + 1).min(last);
+                continue;
+            }
+            // only reaches the page while it has changes, see `UnsavedChanges`
+            VideoEvent::Action(GameAction::Back) => (VideoOption::Back, true),
+            VideoEvent::Action(GameAction::Left) => (state.selected_option(), false),
+            VideoEvent::Action(GameAction::Right) | VideoEvent::Action(GameAction::Confirm) => {
+                (state.selected_option(), true)
+            }
+            VideoEvent::MouseEvent(mouse_event) => match mouse_event.kind {
+                MouseEventKind::Down(button @ (MouseButton::Left | MouseButton::Right)) => {
+                    let Some((index, option)) =
+                        state.option_at(mouse_event.column, mouse_event.row)
+                    else {
+                        continue;
+                    };
+                    state.selected = index;
+                    (option, button == MouseButton::Left)
+                }
+                _ => continue,
+            },
+            VideoEvent::Action(_) | VideoEvent::KeyEvent(_) => continue,
+        };
+
+        match option {
+            VideoOption::Apply => {
+                commands.send(SettingsCommand::Apply);
+            }
+            VideoOption::Back => {
+                commands.send(SettingsCommand::Cancel);
+            }
+            _ => {
+                let mut next = draft.0.clone();
+                option.cycle(&mut next, &registry.names(), forward);
+                draft.set_if_neq(SettingsDraft(next));
+            }
+        }
+    }
+}
+
+fn render_video(
+    screen: Res<State<Screen>>,
+    mut context: ResMut<TerminalContext>,
+    settings: Res<Settings>,
+    draft: Res<SettingsDraft>,
+    theme: Res<Theme>,
+    mut state: ResMut<VideoScreenState>,
+) -> color_eyre::Result<()> {
+    if screen.get() != &Screen::Video {
+        return Ok(());
+    }
+    context.draw(|frame| {
+        let area = frame.area();
+        VideoWidget {
+            settings: &draft,
+            theme: &theme,
+        }
+        .render_ref(area, frame.buffer_mut(), &mut state);
+        apply_video_settings(&settings.video, frame.buffer_mut());
+    })?;
+    Ok(())
+}
+
+fn apply_cursor_style(
+    settings: Res<Settings>,
+    mut context: ResMut<TerminalContext>,
+) -> color_eyre::Result<()> {
+    context.execute(settings.video.cursor_style(settings.cursor_blink()))?;
+    if settings.cursor_blink() {
+        context.execute(EnableBlinking)?;
+    } else {
+        context.execute(DisableBlinking)?;
+    }
+    Ok(())
+}
+
+/// Sleeps for whatever is left of the frame budget.
+fn limit_frame_rate(settings: Res<Settings>, mut frame_start: Local<Option<Instant>>) {
+    if let Some(start) = *frame_start {
+        let remaining = settings.video.frame_time().saturating_sub(start.elapsed());
+        if remaining > Duration::ZERO {
+            std::thread::sleep(remaining);
+        }
+    }
+    *frame_start = Some(Instant::now());
+}
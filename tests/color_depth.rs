@@ -0,0 +1,85 @@
+mod common;
+
+use bevy_starter::{
+    config::{settings::Settings, theme::Theme, video::DetectedColorDepth},
+    mini_salsa::{
+        color_depth::ColorDepth,
+        theme::{Scheme, HIGH_CONTRAST, IMPERIAL, LIGHT, MONOCHROME},
+    },
+};
+use common::TestApp;
+use ratatui::style::Color;
+
+fn colors(scheme: &Scheme) -> Vec<Color> {
+    let mut scheme = scheme.clone();
+    Scheme::PALETTES
+        .iter()
+        .flat_map(|name| *scheme.palette_mut(name).unwrap())
+        .collect()
+}
+
+#[test]
+fn detection_reads_colorterm_and_term() {
+    let detect = ColorDepth::detect_from;
+    assert_eq!(detect(Some("truecolor"), Some("xterm-256color")), ColorDepth::TrueColor);
+    assert_eq!(detect(Some("24bit"), None), ColorDepth::TrueColor);
+    assert_eq!(detect(None, Some("xterm-direct")), ColorDepth::TrueColor);
+    assert_eq!(detect(None, Some("xterm-256color")), ColorDepth::Ansi256);
+    assert_eq!(detect(Some("yes"), Some("screen-256color")), ColorDepth::Ansi256);
+    assert_eq!(detect(None, Some("xterm")), ColorDepth::Ansi16);
+    assert_eq!(detect(None, Some("linux")), ColorDepth::Ansi16);
+    assert_eq!(detect(None, None), ColorDepth::Ansi16);
+}
+
+#[test]
+fn auto_resolves_to_the_detected_depth() {
+    assert_eq!(ColorDepth::Auto.resolve(ColorDepth::Ansi256), ColorDepth::Ansi256);
+    assert_eq!(ColorDepth::Ansi16.resolve(ColorDepth::TrueColor), ColorDepth::Ansi16);
+    assert_eq!(ColorDepth::Auto.resolve(ColorDepth::Auto), ColorDepth::TrueColor);
+}
+
+#[test]
+fn downsampled_schemes_only_use_colors_of_the_depth() {
+    for scheme in [IMPERIAL, LIGHT, HIGH_CONTRAST, MONOCHROME] {
+        let indexed = scheme.downsampled(ColorDepth::Ansi256);
+        assert!(colors(&indexed)
+            .iter()
+            .all(|color| matches!(color, Color::Indexed(n) if *n >= 16)));
+
+        let named = scheme.downsampled(ColorDepth::Ansi16);
+        assert!(colors(&named)
+            .iter()
+            .all(|color| !matches!(color, Color::Rgb(..) | Color::Indexed(_))));
+
+        assert_eq!(scheme.downsampled(ColorDepth::TrueColor), scheme);
+    }
+}
+
+#[test]
+fn downsampled_schemes_keep_readable_text() {
+    for depth in [ColorDepth::Ansi256, ColorDepth::Ansi16] {
+        let dark = IMPERIAL.downsampled(depth);
+        assert!(!dark.is_light());
+        assert_eq!(dark.text_color(dark.black[0]), dark.white[3]);
+
+        let light = LIGHT.downsampled(depth);
+        assert!(light.is_light(), "{:?}", depth);
+        assert_eq!(light.text_color(light.black[0]), light.white[3]);
+    }
+}
+
+#[test]
+fn the_theme_follows_the_color_depth() {
+    let mut app = TestApp::new();
+    assert_eq!(app.resource::<Theme>().scheme, IMPERIAL);
+
+    app.resource_mut::<Settings>().video.color_depth = ColorDepth::Ansi16;
+    app.update();
+    assert_eq!(app.resource::<Theme>().scheme, IMPERIAL.downsampled(ColorDepth::Ansi16));
+    assert!(matches!(app.buffer()[(0, 0)].bg, Color::Black | Color::DarkGray));
+
+    app.resource_mut::<Settings>().video.color_depth = ColorDepth::Auto;
+    *app.resource_mut::<DetectedColorDepth>() = DetectedColorDepth(ColorDepth::Ansi256);
+    app.update();
+    assert_eq!(app.resource::<Theme>().scheme, IMPERIAL.downsampled(ColorDepth::Ansi256));
+}
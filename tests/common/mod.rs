@@ -0,0 +1,183 @@
+// Each test binary uses a different part of the harness.
+#![allow(dead_code)]
+
+pub mod snapshot;
+
+use std::time::Duration;
+
+use bevy::{app::AppExit, prelude::*, state::app::StatesPlugin};
+use bevy_ratatui::event::{KeyEvent, MouseEvent};
+use bevy_starter::{
+    audio::backend::{Audio, AudioCommand, RecordingAudioBackend},
+    config::{settings::SettingsPath, theme::ThemesConfigPath, video::DetectedColorDepth},
+    mini_salsa::color_depth::ColorDepth,
+    states::{app_state::AppState, screen::Screen},
+    terminal::context::TerminalContext,
+    widgets::{splash::SplashDuration, video::FrameRateLimit},
+    GamePlugin,
+};
+use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::buffer::Buffer;
+
+pub const WIDTH: u16 = 100;
+pub const HEIGHT: u16 = 40;
+
+/// The app with all screens, drawing to a [`ratatui::backend::TestBackend`].
+///
+/// Nothing is read from or written to the config directory, sounds are recorded
+/// instead of played, the terminal is taken to support true color and frames
+/// are not held back to the frame rate.
+pub struct TestApp {
+    pub app: App,
+    audio: RecordingAudioBackend,
+}
+
+impl TestApp {
+    pub fn new() -> Self {
+        Self::with_size(WIDTH, HEIGHT)
+    }
+
+    /// Builds the app and runs the first frames, so the splash screen is over
+    /// and the Home screen is drawn.
+    pub fn with_size(width: u16, height: u16) -> Self {
+        let audio = RecordingAudioBackend::default();
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(StatesPlugin)
+            .insert_resource(TerminalContext::test(width, height))
+            .insert_resource(SettingsPath(None))
+            .insert_resource(ThemesConfigPath(None))
+            .insert_resource(DetectedColorDepth(ColorDepth::TrueColor))
+            .insert_resource(SplashDuration(Duration::ZERO))
+            .insert_resource(FrameRateLimit(false))
+            .add_plugins(GamePlugin)
+            .insert_resource(Audio::new(audio.clone()));
+        app.update();
+        app.update();
+        Self { app, audio }
+    }
+
+    /// Runs one frame.
+    pub fn update(&mut self) -> &mut Self {
+        self.app.update();
+        self
+    }
+
+    /// Queues a key event for the next frame.
+    pub fn send_key(&mut self, event: crossterm::event::KeyEvent) -> &mut Self {
+        self.app.world_mut().send_event(KeyEvent(event));
+        self
+    }
+
+    /// Queues any event for the next frame.
+    pub fn send_event<E: Event>(&mut self, event: E) -> &mut Self {
+        self.app.world_mut().send_event(event);
+        self
+    }
+
+    /// Presses and releases a key, then runs a frame.
+    pub fn press(&mut self, code: KeyCode) -> &mut Self {
+        self.press_with(code, KeyModifiers::NONE)
+    }
+
+    pub fn press_with(&mut self, code: KeyCode, modifiers: KeyModifiers) -> &mut Self {
+        let press = crossterm::event::KeyEvent::new(code, modifiers);
+        let release = crossterm::event::KeyEvent::new_with_kind(code, modifiers, KeyEventKind::Release);
+        self.send_key(press).send_key(release).update()
+    }
+
+    /// Queues a mouse event at a cell for the next frame.
+    pub fn send_mouse(&mut self, kind: MouseEventKind, column: u16, row: u16) -> &mut Self {
+        self.app.world_mut().send_event(MouseEvent(crossterm::event::MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }));
+        self
+    }
+
+    /// Moves the mouse to a cell, then runs a frame.
+    pub fn hover(&mut self, column: u16, row: u16) -> &mut Self {
+        self.send_mouse(MouseEventKind::Moved, column, row).update()
+    }
+
+    /// Presses and releases the left button on a cell within one frame.
+    pub fn click(&mut self, column: u16, row: u16) -> &mut Self {
+        self.send_mouse(MouseEventKind::Down(MouseButton::Left), column, row)
+            .send_mouse(MouseEventKind::Up(MouseButton::Left), column, row)
+            .update()
+    }
+
+    /// Clicks the first cell showing `text`.
+    pub fn click_text(&mut self, text: &str) -> &mut Self {
+        let (column, row) = self
+            .find_text(text)
+            .unwrap_or_else(|| panic!("`{}` is not on screen:\n{}", text, self.screen()));
+        self.click(column, row)
+    }
+
+    /// The screen that is shown.
+    pub fn state(&self) -> Screen {
+        *self.app.world().resource::<State<Screen>>().get()
+    }
+
+    pub fn app_state(&self) -> AppState {
+        *self.app.world().resource::<State<AppState>>().get()
+    }
+
+    pub fn resource<R: Resource>(&self) -> &R {
+        self.app.world().resource::<R>()
+    }
+
+    pub fn resource_mut<R: Resource>(&mut self) -> Mut<'_, R> {
+        self.app.world_mut().resource_mut::<R>()
+    }
+
+    /// Whether something asked the app to quit.
+    pub fn exit_requested(&self) -> bool {
+        !self.app.world().resource::<Events<AppExit>>().is_empty()
+    }
+
+    pub fn audio_commands(&self) -> Vec<AudioCommand> {
+        self.audio.commands()
+    }
+
+    pub fn resize(&mut self, width: u16, height: u16) -> &mut Self {
+        self.app.world_mut().resource_mut::<TerminalContext>().resize_test(width, height);
+        self.update()
+    }
+
+    /// The last drawn frame.
+    pub fn buffer(&self) -> &Buffer {
+        self.app
+            .world()
+            .resource::<TerminalContext>()
+            .test_buffer()
+            .expect("the test app draws to a test backend")
+    }
+
+    /// The last drawn frame as text, one line per row.
+    pub fn screen(&self) -> String {
+        let buffer = self.buffer();
+        let area = buffer.area;
+        (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Column and row of the first cell of `text` on screen.
+    pub fn find_text(&self, text: &str) -> Option<(u16, u16)> {
+        self.screen().lines().enumerate().find_map(|(row, line)| {
+            let byte = line.find(text)?;
+            Some((line[..byte].chars().count() as u16, row as u16))
+        })
+    }
+}
@@ -0,0 +1,132 @@
+//! Golden-file snapshots of rendered buffers.
+//!
+//! Snapshots live in `tests/snapshots/<name>.txt`. Run the tests with
+//! `BLESS_SNAPSHOTS=1` to write new snapshots or accept changed ones.
+
+use std::{env, fmt::Write, fs, path::PathBuf};
+
+use ratatui::{
+    buffer::{Buffer, Cell},
+    style::Style,
+};
+
+/// Set to `1` to write the current output instead of comparing against it.
+pub const BLESS_VAR: &str = "BLESS_SNAPSHOTS";
+
+/// Compares the text of `buffer` with the snapshot `name`.
+pub fn assert_snapshot(name: &str, buffer: &Buffer) {
+    assert_matches(name, &buffer_text(buffer));
+}
+
+/// Compares the text of `buffer` and the styles of its cells with the snapshot `name`.
+pub fn assert_styled_snapshot(name: &str, buffer: &Buffer) {
+    let mut contents = buffer_text(buffer);
+    contents.push_str("--- styles ---\n");
+    contents.push_str(&style_annotations(buffer));
+    assert_matches(name, &contents);
+}
+
+/// The symbols of the buffer, one line per row, framed so trailing spaces stay visible.
+pub fn buffer_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        text.push('|');
+        for x in area.left()..area.right() {
+            text.push_str(buffer[(x, y)].symbol());
+        }
+        text.push_str("|\n");
+    }
+    text
+}
+
+/// Runs of equally styled cells, one line per run that isn't the style of an empty cell.
+///
+/// `y x1..x2: fg=… bg=… mods=…`, with `x2` exclusive.
+pub fn style_annotations(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let empty: Style = Cell::default().style();
+    let mut annotations = String::new();
+    for y in area.top()..area.bottom() {
+        let mut x = area.left();
+        while x < area.right() {
+            let style = buffer[(x, y)].style();
+            let start = x;
+            while x < area.right() && buffer[(x, y)].style() == style {
+                x += 1;
+            }
+            if style != empty {
+                writeln!(annotations, "{} {}..{}: {}", y, start, x, describe_style(style))
+                    .expect("writing to a string");
+            }
+        }
+    }
+    annotations
+}
+
+fn describe_style(style: Style) -> String {
+    let mut parts = Vec::new();
+    if let Some(fg) = style.fg {
+        parts.push(format!("fg={}", fg));
+    }
+    if let Some(bg) = style.bg {
+        parts.push(format!("bg={}", bg));
+    }
+    if !style.add_modifier.is_empty() {
+        parts.push(format!("mods={:?}", style.add_modifier));
+    }
+    parts.join(" ")
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{}.txt", name))
+}
+
+fn assert_matches(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    if env::var(BLESS_VAR).is_ok_and(|value| value == "1") {
+        fs::create_dir_all(path.parent().expect("snapshots have a directory"))
+            .expect("creating the snapshot directory");
+        fs::write(&path, actual).expect("writing the snapshot");
+        return;
+    }
+    let Ok(expected) = fs::read_to_string(&path) else {
+        panic!(
+            "missing snapshot {}, run with {}=1 to create it:\n{}",
+            path.display(),
+            BLESS_VAR,
+            actual
+        );
+    };
+    if expected != actual {
+        panic!(
+            "snapshot {} changed, run with {}=1 to accept:\n{}",
+            path.display(),
+            BLESS_VAR,
+            line_diff(&expected, actual)
+        );
+    }
+}
+
+/// Expected and actual lines side by side wherever they differ.
+fn line_diff(expected: &str, actual: &str) -> String {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+    let mut diff = String::new();
+    for index in 0..expected.len().max(actual.len()) {
+        let (old, new) = (expected.get(index), actual.get(index));
+        if old == new {
+            continue;
+        }
+        if let Some(old) = old {
+            writeln!(diff, "{:>4} - {}", index + 1, old).expect("writing to a string");
+        }
+        if let Some(new) = new {
+            writeln!(diff, "{:>4} + {}", index + 1, new).expect("writing to a string");
+        }
+    }
+    diff
+}
@@ -0,0 +1,68 @@
+use bevy_starter::{
+    config::theme::ThemeRegistry,
+    mini_salsa::{
+        contrast::{check_contrast, contrast_ratio, relative_luminance, MIN_TEXT_CONTRAST},
+        theme::IMPERIAL,
+    },
+};
+use ratatui::style::Color;
+
+#[test]
+fn contrast_ratios_follow_wcag() {
+    assert_eq!(relative_luminance(Color::Rgb(0, 0, 0)), Some(0.0));
+    assert_eq!(relative_luminance(Color::White), Some(1.0));
+    assert_eq!(relative_luminance(Color::Reset), None);
+
+    let ratio = contrast_ratio(Color::Black, Color::White).unwrap();
+    assert!((ratio - 21.0).abs() < 1e-9);
+    assert_eq!(contrast_ratio(Color::White, Color::Black), Some(ratio));
+    // #777777 on white is the usual example of gray text that just fails AA
+    let gray = contrast_ratio(Color::Rgb(0x77, 0x77, 0x77), Color::White).unwrap();
+    assert!((4.47..4.49).contains(&gray), "{gray}");
+    // named and indexed colors use their palette values
+    assert_eq!(
+        contrast_ratio(Color::Indexed(15), Color::Indexed(16)),
+        Some(ratio)
+    );
+}
+
+#[test]
+fn text_color_picks_the_higher_contrast() {
+    for bg in [
+        Color::Rgb(0x77, 0x77, 0x77),
+        Color::Cyan,
+        Color::Indexed(21),
+        IMPERIAL.primary[2],
+        IMPERIAL.secondary[3],
+    ] {
+        let fg = IMPERIAL.text_color(bg);
+        let other = if fg == IMPERIAL.white[3] {
+            IMPERIAL.black[0]
+        } else {
+            IMPERIAL.white[3]
+        };
+        assert!(contrast_ratio(fg, bg) >= contrast_ratio(other, bg), "{bg}");
+    }
+}
+
+#[test]
+fn failing_pairs_are_reported() {
+    let mut scheme = IMPERIAL;
+    scheme.white = [Color::Rgb(0x30, 0x30, 0x30); 4];
+    let issues = check_contrast(&scheme, MIN_TEXT_CONTRAST);
+    let data = issues.iter().find(|issue| issue.style == "data").unwrap();
+    assert_eq!(data.fg, scheme.white[0]);
+    assert_eq!(data.bg, scheme.black[1]);
+    assert!(data.ratio < MIN_TEXT_CONTRAST);
+    assert!(data.to_string().starts_with("data: #303030 on #18191F"), "{data}");
+}
+
+#[test]
+fn built_in_schemes_pass_wcag_aa() {
+    let registry = ThemeRegistry::default();
+    for name in registry.names() {
+        let issues = check_contrast(registry.get(&name).unwrap(), MIN_TEXT_CONTRAST);
+        let report: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        assert!(issues.is_empty(), "{name}:\n{}", report.join("\n"));
+    }
+}
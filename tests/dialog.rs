@@ -0,0 +1,106 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_starter::{
+    states::screen::Screen,
+    widgets::{
+        dialog::{Dialog, DialogClosed, Dialogs},
+        game::GameSession,
+    },
+};
+use common::TestApp;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn closed_dialogs(app: &TestApp) -> Vec<DialogClosed> {
+    let events = app.resource::<Events<DialogClosed>>();
+    events.get_cursor().read(events).copied().collect()
+}
+
+#[test]
+fn leaving_a_game_asks_first() {
+    let mut app = TestApp::new();
+    app.click_text("New Game").press(KeyCode::Esc).click_text("Main Menu");
+    assert!(app.resource::<Dialogs>().is_open());
+    assert_eq!(app.state(), Screen::Pause);
+
+    // No is focused, Enter keeps the game
+    app.press(KeyCode::Enter);
+    assert!(!app.resource::<Dialogs>().is_open());
+    assert_eq!(app.state(), Screen::Pause);
+    assert!(app.app.world().contains_resource::<GameSession>());
+
+    app.click_text("Main Menu").press(KeyCode::Left).press(KeyCode::Enter);
+    assert_eq!(app.state(), Screen::Home);
+    assert!(!app.app.world().contains_resource::<GameSession>());
+}
+
+#[test]
+fn a_new_game_from_the_pause_options_asks_and_starts_over() {
+    let mut app = TestApp::new();
+    app.click_text("New Game").press(KeyCode::Esc).click_text("Options");
+    app.resource_mut::<GameSession>().played = Duration::from_secs(600);
+
+    app.click_text("New Game");
+    assert!(app.resource::<Dialogs>().is_open());
+    assert_eq!(app.state(), Screen::Options);
+
+    app.click_text("No");
+    assert_eq!(app.state(), Screen::Options);
+    assert_eq!(app.resource::<GameSession>().played, Duration::from_secs(600));
+
+    app.click_text("New Game").click_text("Yes");
+    assert_eq!(app.state(), Screen::Game);
+    assert!(app.resource::<GameSession>().played < Duration::from_secs(600));
+}
+
+#[test]
+fn the_dialog_is_drawn_over_the_screen() {
+    let mut app = TestApp::new();
+    app.click_text("New Game").press(KeyCode::Esc).click_text("Main Menu");
+    for text in ["Paused", "Leave game", "Return to the main menu?", "Yes", "No"] {
+        assert!(app.find_text(text).is_some(), "`{}` missing:\n{}", text, app.screen());
+    }
+
+    app.press(KeyCode::Esc);
+    assert!(app.find_text("Leave game").is_none(), "{}", app.screen());
+    assert!(app.find_text("Paused").is_some(), "{}", app.screen());
+}
+
+#[test]
+fn ctrl_c_quits_with_a_dialog_open() {
+    let mut app = TestApp::new();
+    app.resource_mut::<Dialogs>().open(Dialog::confirm("ask", "Ask", "Really?"));
+    app.update();
+    app.press_with(KeyCode::Char('c'), KeyModifiers::CONTROL);
+    assert!(app.exit_requested());
+}
+
+#[test]
+fn an_open_dialog_takes_all_input() {
+    let mut app = TestApp::new();
+    app.resource_mut::<Dialogs>()
+        .open(Dialog::new("pick", "Pick", "Choose one").button("First").button("Second"));
+    app.update();
+
+    // Esc cancels the dialog instead of opening the options
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Home);
+    assert_eq!(
+        closed_dialogs(&app),
+        [DialogClosed { id: "pick", choice: None }]
+    );
+
+    app.resource_mut::<Dialogs>()
+        .open(Dialog::new("pick", "Pick", "Choose one").button("First").button("Second"));
+    app.update();
+    // Down and Space move in the dialog, the home menu never sees them
+    app.press(KeyCode::Down).press(KeyCode::Char(' '));
+    assert_eq!(app.state(), Screen::Home);
+    assert_eq!(
+        closed_dialogs(&app).last(),
+        Some(&DialogClosed { id: "pick", choice: Some(1) })
+    );
+    assert!(!app.resource::<Dialogs>().is_open());
+}
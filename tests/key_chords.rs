@@ -0,0 +1,104 @@
+use bevy_starter::config::key_bindings::{format_key_chord, parse_key_bindings, parse_key_chord};
+use crossterm::event::{KeyCode, KeyModifiers, MediaKeyCode, ModifierKeyCode};
+
+fn round_trip(chord: &str) -> String {
+    let (code, modifiers) = parse_key_chord(chord).unwrap();
+    format_key_chord(code, modifiers)
+}
+
+#[test]
+fn chords_survive_a_round_trip() {
+    let chords = [
+        "q", "Ctrl++", "+", "Space", "Shift+Tab", "BackTab", "F1", "Ctrl+F12", "Ctrl+Alt+Delete",
+    ];
+    for chord in chords {
+        assert_eq!(round_trip(chord), chord);
+    }
+    assert_eq!(parse_key_chord("Ctrl++").unwrap(), (KeyCode::Char('+'), KeyModifiers::CONTROL));
+    assert_eq!(parse_key_chord("Space").unwrap(), (KeyCode::Char(' '), KeyModifiers::empty()));
+    assert_eq!(parse_key_chord("Shift+Tab").unwrap(), (KeyCode::Tab, KeyModifiers::SHIFT));
+    assert_eq!(parse_key_chord("F5").unwrap(), (KeyCode::F(5), KeyModifiers::empty()));
+}
+
+#[test]
+fn media_and_modifier_keys_survive_a_round_trip() {
+    let keys = [
+        KeyCode::Media(MediaKeyCode::PlayPause),
+        KeyCode::Media(MediaKeyCode::Pause),
+        KeyCode::Modifier(ModifierKeyCode::LeftShift),
+        KeyCode::Modifier(ModifierKeyCode::IsoLevel3Shift),
+    ];
+    for code in keys {
+        let chord = format_key_chord(code, KeyModifiers::ALT);
+        assert_eq!(parse_key_chord(&chord).unwrap(), (code, KeyModifiers::ALT), "{}", chord);
+    }
+    assert_eq!(round_trip("Media(PlayPause)"), "Media(PlayPause)");
+    assert_eq!(round_trip("media(playpause)"), "Media(PlayPause)");
+    assert!(parse_key_chord("Media(Eject)").is_err());
+}
+
+#[test]
+fn names_ignore_case_but_characters_do_not() {
+    assert_eq!(round_trip("ctrl+ALT+delete"), "Ctrl+Alt+Delete");
+    assert_eq!(round_trip("shift+tab"), "Shift+Tab");
+    assert_eq!(round_trip("space"), "Space");
+    assert_eq!(round_trip("f5"), "F5");
+    assert_eq!(parse_key_chord("Ctrl+S").unwrap(), (KeyCode::Char('S'), KeyModifiers::CONTROL));
+    assert_ne!(parse_key_chord("S").unwrap(), parse_key_chord("s").unwrap());
+}
+
+#[test]
+fn unknown_modifiers_and_keys_are_rejected() {
+    for chord in ["", "  ", "Hyperdrive", "Cmd+s", "Ctrl+", "Ctrl+Shift+Nope", "Fx", "F999"] {
+        assert!(parse_key_chord(chord).is_err(), "`{}` was accepted", chord);
+    }
+    let err = parse_key_chord("Cmd+s").unwrap_err();
+    assert!(format!("{:#}", err).contains("unknown modifier `Cmd`"), "{:#}", err);
+    let err = parse_key_chord("Ctrl+Nope").unwrap_err();
+    assert!(format!("{:#}", err).contains("unknown key `Nope`"), "{:#}", err);
+}
+
+#[test]
+fn each_bad_entry_is_reported_and_skipped() {
+    let loaded = parse_key_bindings(
+        r#"
+        [[binding]]
+        user_key = "x"
+        system_key = "x"
+
+        [[binding]]
+        action = "Jump"
+        user_key = "j"
+        system_key = "Space"
+
+        [[binding]]
+        action = "Fly"
+        user_key = "Cmd+f"
+        system_key = "f"
+
+        [[binding]]
+        action = "Dig"
+        user_key = "d"
+        system_key = "Shovel"
+
+        [[binding]]
+        action = "Leap"
+        user_key = "j"
+        system_key = "l"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(loaded.widget.key_bindings.len(), 1);
+    let errors = &loaded.errors;
+    assert_eq!(errors.len(), 4, "{:?}", errors);
+    assert!(errors[0].starts_with("binding #1: missing field `action`"), "{}", errors[0]);
+    assert!(errors[1].starts_with("binding `Fly` on `Cmd+f`: invalid user_key"), "{}", errors[1]);
+    assert!(errors[1].contains("unknown modifier `Cmd`"), "{}", errors[1]);
+    assert!(errors[2].starts_with("binding `Dig` on `d`: invalid system_key"), "{}", errors[2]);
+    assert!(errors[2].contains("unknown key `Shovel`"), "{}", errors[2]);
+    assert!(
+        errors[3].starts_with("binding `Leap` on `j`: Key binding already exists"),
+        "{}",
+        errors[3]
+    );
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::time::TimeUpdateStrategy;
+use bevy_starter::{
+    config::{
+        key_bindings::{format_key_sequence, parse_key_sequence, KeyBindingEntry},
+        settings::Settings,
+    },
+    input::actions::PendingSequence,
+    widgets::{
+        key_bindings::{KeyBinding, KeyBindingsTrait, KeyBindingsWidget},
+        dialog::{Dialog, Dialogs},
+        video::VideoScreenState,
+    },
+};
+use common::TestApp;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn binding(action: &str, user_key: &str) -> KeyBinding {
+    let mut key_binding = KeyBinding {
+        defined_action_description: action.to_string(),
+        system_key: KeyCode::Down,
+        is_customizable: true,
+        ..Default::default()
+    };
+    key_binding.set_user_sequence(&parse_key_sequence(user_key).unwrap());
+    key_binding
+}
+
+/// The Video screen, with `g g` moving down next to the default bindings.
+fn video_screen_with_g_g() -> TestApp {
+    let mut app = TestApp::new();
+    app.resource_mut::<Settings>().controls.bindings.push(KeyBindingEntry {
+        action: "Go down".to_string(),
+        user_key: "g g".to_string(),
+        system_key: "Down".to_string(),
+        customizable: true,
+    });
+    app.press(KeyCode::Esc).click_text("Video");
+    app
+}
+
+fn pending(app: &TestApp) -> Option<String> {
+    app.resource::<PendingSequence>().sequence().map(format_key_sequence)
+}
+
+#[test]
+fn sequences_are_chords_separated_by_spaces() {
+    let sequence = parse_key_sequence(" Ctrl+x   Ctrl+s ").unwrap();
+    assert_eq!(
+        sequence.chords(),
+        [
+            (KeyCode::Char('x'), KeyModifiers::CONTROL),
+            (KeyCode::Char('s'), KeyModifiers::CONTROL)
+        ]
+    );
+    assert_eq!(format_key_sequence(&sequence), "Ctrl+x Ctrl+s");
+    assert_eq!(format_key_sequence(&parse_key_sequence("Space").unwrap()), "Space");
+    assert!(parse_key_sequence("  ").is_err());
+    assert!(parse_key_sequence("g Nope+g").is_err());
+}
+
+#[test]
+fn a_sequence_and_its_start_cannot_both_be_bound() {
+    let mut key_bindings = KeyBindingsWidget::new();
+    key_bindings.add_custom_key_binding(binding("Top", "g g")).unwrap();
+    assert!(key_bindings.add_custom_key_binding(binding("Go", "g")).is_err());
+    assert!(key_bindings.add_custom_key_binding(binding("Deeper", "g g g")).is_err());
+    key_bindings.add_custom_key_binding(binding("Other", "g x")).unwrap();
+    key_bindings.add_custom_key_binding(binding("Plain", "x")).unwrap();
+
+    // rebinding checks the same
+    let plain = parse_key_sequence("x").unwrap();
+    let g = parse_key_sequence("g").unwrap();
+    let err = key_bindings.rebind_user_key(&plain, &g).unwrap_err();
+    assert!(err.to_string().contains("'Top'") || err.to_string().contains("'Other'"), "{}", err);
+    let g_y = parse_key_sequence("g y").unwrap();
+    key_bindings.rebind_user_key(&plain, &g_y).unwrap();
+    assert_eq!(key_bindings.key_bindings.len(), 3);
+}
+
+#[test]
+fn a_completed_sequence_sends_its_action() {
+    let mut app = video_screen_with_g_g();
+    app.press(KeyCode::Char('g'));
+    assert_eq!(pending(&app).as_deref(), Some("g"));
+    assert!(app.find_text(" g …").is_some(), "{}", app.screen());
+    assert_eq!(app.resource::<VideoScreenState>().selected, 0);
+
+    app.press(KeyCode::Char('g'));
+    assert_eq!(pending(&app), None);
+    assert_eq!(app.resource::<VideoScreenState>().selected, 1);
+    assert!(app.find_text(" g …").is_none(), "{}", app.screen());
+}
+
+#[test]
+fn the_indicator_is_drawn_over_a_dialog() {
+    let mut app = video_screen_with_g_g();
+    app.resource_mut::<Dialogs>().open(Dialog::confirm("ask", "Ask", "Really?"));
+    app.update();
+    app.press(KeyCode::Char('g'));
+    assert_eq!(pending(&app).as_deref(), Some("g"));
+    for text in ["Video", "Really?", " g …"] {
+        assert!(app.find_text(text).is_some(), "`{}` missing:\n{}", text, app.screen());
+    }
+}
+
+#[test]
+fn a_key_outside_the_sequence_drops_it() {
+    let mut app = video_screen_with_g_g();
+    app.press(KeyCode::Char('g')).press(KeyCode::Down);
+    assert_eq!(pending(&app), None);
+    assert_eq!(app.resource::<VideoScreenState>().selected, 1);
+    // the next g starts over
+    app.press(KeyCode::Char('g'));
+    assert_eq!(pending(&app).as_deref(), Some("g"));
+}
+
+#[test]
+fn a_started_sequence_times_out() {
+    let mut app = video_screen_with_g_g();
+    app.resource_mut::<Settings>().controls.sequence_timeout_ms = 1000;
+    app.app
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(400)));
+    app.press(KeyCode::Char('g'));
+    app.update().update();
+    assert_eq!(pending(&app).as_deref(), Some("g"));
+    app.update().update();
+    assert_eq!(pending(&app), None);
+
+    app.press(KeyCode::Char('g'));
+    assert_eq!(app.resource::<VideoScreenState>().selected, 0);
+    assert_eq!(pending(&app).as_deref(), Some("g"));
+}
@@ -0,0 +1,203 @@
+mod common;
+
+use bevy_starter::{
+    audio::backend::{AudioChannel, AudioCommand},
+    config::{settings::Settings, video::BorderSet},
+    states::screen::Screen,
+    widgets::{
+        home::HomeComponents,
+        menu::{MenuItemState, MenuState},
+        options::OptionComponents,
+    },
+};
+use common::TestApp;
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEventKind};
+
+fn home_menu(app: &TestApp) -> &MenuState<HomeComponents> {
+    app.resource::<MenuState<HomeComponents>>()
+}
+
+fn options_menu(app: &TestApp) -> &MenuState<OptionComponents> {
+    app.resource::<MenuState<OptionComponents>>()
+}
+
+#[test]
+fn starts_on_the_home_screen() {
+    let app = TestApp::new();
+    assert_eq!(app.state(), Screen::Home);
+    for label in ["Continue", "New Game", "Options", "Quit", "bevy_starter v"] {
+        assert!(app.find_text(label).is_some(), "`{}` missing:\n{}", label, app.screen());
+    }
+}
+
+#[test]
+fn the_main_menu_opens_options_and_quits() {
+    let mut app = TestApp::new();
+    // there are no saved games, so focus starts on New Game
+    assert_eq!(home_menu(&app).focused_id(), Some(HomeComponents::NewGame));
+    app.press(KeyCode::Down).press(KeyCode::Enter);
+    assert_eq!(app.state(), Screen::Options);
+
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Home);
+    app.press(KeyCode::Down).press(KeyCode::Char(' '));
+    assert!(app.exit_requested());
+
+    let mut app = TestApp::new();
+    app.click_text("Continue");
+    assert_eq!(app.state(), Screen::Home);
+    assert!(!app.exit_requested());
+    app.click_text("Quit");
+    assert!(app.exit_requested());
+}
+
+#[test]
+fn title_art_follows_the_border_set() {
+    let mut app = TestApp::new();
+    assert!(app.find_text("████").is_some(), "{}", app.screen());
+
+    app.resource_mut::<Settings>().video.border_set = BorderSet::Ascii;
+    app.update();
+    assert!(app.find_text("####").is_some(), "{}", app.screen());
+
+    app.resize(40, 20);
+    assert!(app.find_text("BEVY STARTER").is_some(), "{}", app.screen());
+}
+
+#[test]
+fn escape_opens_options_and_goes_back_home() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Options);
+    for label in ["New Game", "Key Bindings", "Video", "Audio", "Back"] {
+        assert!(app.find_text(label).is_some(), "`{}` missing:\n{}", label, app.screen());
+    }
+
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Home);
+}
+
+#[test]
+fn q_and_ctrl_c_quit() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Char('q'));
+    assert!(app.exit_requested());
+
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc).press_with(KeyCode::Char('c'), KeyModifiers::CONTROL);
+    assert!(app.exit_requested());
+}
+
+#[test]
+fn keyboard_focus_wraps_around_the_options_menu() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc);
+    assert_eq!(options_menu(&app).focused_id(), Some(OptionComponents::NewGame));
+
+    app.press(KeyCode::Up);
+    assert_eq!(options_menu(&app).focused_id(), Some(OptionComponents::Back));
+    app.press(KeyCode::Tab).press(KeyCode::Tab).press(KeyCode::Tab);
+    assert_eq!(options_menu(&app).focused_id(), Some(OptionComponents::Video));
+    app.press(KeyCode::BackTab).press(KeyCode::Down).press(KeyCode::Down);
+    assert_eq!(options_menu(&app).focused_id(), Some(OptionComponents::Audio));
+}
+
+#[test]
+fn enter_and_space_activate_the_focused_item() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc).press(KeyCode::Down).press(KeyCode::Enter);
+    assert_eq!(app.state(), Screen::KeyBindings);
+
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Options);
+    assert_eq!(options_menu(&app).focused_id(), Some(OptionComponents::KeyBindings));
+    app.press(KeyCode::Down).press(KeyCode::Char(' '));
+    assert_eq!(app.state(), Screen::Video);
+}
+
+#[test]
+fn new_game_in_the_options_menu_starts_a_game() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc).click_text("New Game");
+    assert_eq!(app.state(), Screen::Game);
+
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc).press(KeyCode::Enter);
+    assert_eq!(app.state(), Screen::Game);
+}
+
+#[test]
+fn disabled_items_ignore_the_mouse() {
+    let mut app = TestApp::new();
+    // there are no saved games to continue
+    app.click_text("Continue");
+    assert_eq!(app.state(), Screen::Home);
+    let menu = home_menu(&app);
+    assert_eq!(menu.item_state(0), MenuItemState::Disabled);
+    assert_eq!(menu.focused_id(), Some(HomeComponents::NewGame));
+}
+
+#[test]
+fn hovering_moves_keyboard_focus() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc);
+    let (column, row) = app.find_text("Audio").unwrap();
+    app.hover(column, row);
+    let menu = options_menu(&app);
+    assert_eq!(menu.focused_id(), Some(OptionComponents::Audio));
+    assert_eq!(menu.item_state(3), MenuItemState::Hovered);
+
+    app.press(KeyCode::Up);
+    let menu = options_menu(&app);
+    assert_eq!(menu.focused_id(), Some(OptionComponents::Video));
+    assert_eq!(menu.item_state(2), MenuItemState::Focused);
+    assert_eq!(menu.item_state(3), MenuItemState::Normal);
+}
+
+#[test]
+fn clicking_a_menu_item_opens_its_screen() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc).click_text("Audio");
+    assert_eq!(app.state(), Screen::Audio);
+    assert!(app.find_text("Master volume").is_some(), "{}", app.screen());
+
+    app.press(KeyCode::Esc).click_text("Back");
+    assert_eq!(app.state(), Screen::Home);
+}
+
+#[test]
+fn releasing_the_mouse_elsewhere_cancels_the_click() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc);
+    let (video_column, video_row) = app.find_text("Video").unwrap();
+    let (audio_column, audio_row) = app.find_text("Audio").unwrap();
+
+    app.send_mouse(MouseEventKind::Down(MouseButton::Left), video_column, video_row)
+        .update();
+    assert_eq!(options_menu(&app).item_state(2), MenuItemState::Pressed);
+    assert_eq!(app.state(), Screen::Options);
+
+    app.send_mouse(MouseEventKind::Up(MouseButton::Left), audio_column, audio_row)
+        .update();
+    assert_eq!(app.state(), Screen::Options);
+    assert_eq!(options_menu(&app).pressed, None);
+}
+
+#[test]
+fn changing_a_volume_plays_a_preview() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc).click_text("Audio").press(KeyCode::Left);
+    assert!(app.find_text("75%").is_some(), "{}", app.screen());
+    assert!(app.audio_commands().contains(&AudioCommand::Play {
+        sound: "ui_tick".to_string(),
+        channel: AudioChannel::Sfx,
+    }));
+}
+
+#[test]
+fn screens_follow_the_terminal_size() {
+    let mut app = TestApp::new();
+    app.resize(40, 30);
+    assert_eq!(app.buffer().area.width, 40);
+    assert_eq!(app.buffer().area.height, 30);
+}
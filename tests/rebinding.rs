@@ -0,0 +1,211 @@
+use std::time::Duration;
+
+use bevy_starter::{
+    config::key_bindings::{parse_key_bindings, parse_key_sequence},
+    widgets::key_bindings::{KeyBindingsState, KeyBindingsWidget, RebindMode, RebindOutcome},
+};
+use crossterm::event::{KeyCode, KeyModifiers};
+
+// Shown as Quit, Move down, Move up.
+const KEY_BINDINGS: &str = r#"
+[[binding]]
+action = "Quit"
+user_key = "q"
+system_key = "q"
+
+[[binding]]
+action = "Move up"
+user_key = "w"
+system_key = "Up"
+customizable = true
+
+[[binding]]
+action = "Move down"
+user_key = "s"
+system_key = "Down"
+customizable = true
+"#;
+
+const MOVE_DOWN: usize = 1;
+
+fn widget() -> KeyBindingsWidget {
+    parse_key_bindings(KEY_BINDINGS).unwrap().widget
+}
+
+/// The action bound to `user_key`, if any.
+fn action(widget: &KeyBindingsWidget, user_key: &str) -> Option<String> {
+    widget
+        .key_bindings
+        .get(&parse_key_sequence(user_key).unwrap())
+        .map(|key_binding| key_binding.defined_action_description.clone())
+}
+
+fn press(
+    state: &mut KeyBindingsState,
+    widget: &mut KeyBindingsWidget,
+    code: KeyCode,
+) -> RebindOutcome {
+    state.handle_rebind_key(widget, code, KeyModifiers::empty(), Duration::ZERO)
+}
+
+/// Selects Move down and enters `user_key` for it, which belongs to Move up.
+fn conflict_on(widget: &mut KeyBindingsWidget, user_key: KeyCode) -> KeyBindingsState {
+    let mut state = KeyBindingsState::new();
+    state.select(MOVE_DOWN);
+    press(&mut state, widget, KeyCode::Enter);
+    assert_eq!(press(&mut state, widget, user_key), RebindOutcome::Handled);
+    assert_eq!(press(&mut state, widget, KeyCode::Enter), RebindOutcome::Handled);
+    assert!(matches!(state.rebind, RebindMode::Conflict { .. }), "{:?}", state.rebind);
+    state
+}
+
+#[test]
+fn a_free_key_is_bound_on_enter() {
+    let mut widget = widget();
+    let mut state = KeyBindingsState::new();
+    state.select(MOVE_DOWN);
+    assert_eq!(press(&mut state, &mut widget, KeyCode::Enter), RebindOutcome::Handled);
+    assert_eq!(
+        state.rebind,
+        RebindMode::Capturing {
+            user_key: parse_key_sequence("s").unwrap(),
+            captured: Vec::new(),
+            pressed_at: Duration::ZERO,
+        }
+    );
+
+    assert_eq!(press(&mut state, &mut widget, KeyCode::Char('x')), RebindOutcome::Handled);
+    assert_eq!(action(&widget, "x"), None);
+    assert_eq!(press(&mut state, &mut widget, KeyCode::Enter), RebindOutcome::Rebound);
+    assert_eq!(state.rebind, RebindMode::Idle);
+    assert_eq!(action(&widget, "x").as_deref(), Some("Move down"));
+    assert_eq!(action(&widget, "s"), None);
+}
+
+#[test]
+fn chords_are_collected_into_a_sequence() {
+    let mut widget = widget();
+    let mut state = KeyBindingsState::new();
+    state.select(MOVE_DOWN);
+    press(&mut state, &mut widget, KeyCode::Enter);
+    press(&mut state, &mut widget, KeyCode::Char('g'));
+    state.handle_rebind_key(
+        &mut widget,
+        KeyCode::Char('g'),
+        KeyModifiers::empty(),
+        Duration::from_millis(100),
+    );
+    assert_eq!(press(&mut state, &mut widget, KeyCode::Enter), RebindOutcome::Rebound);
+    assert_eq!(action(&widget, "g g").as_deref(), Some("Move down"));
+    assert_eq!(action(&widget, "g"), None);
+    assert_eq!(action(&widget, "s"), None);
+}
+
+#[test]
+fn the_capture_ends_when_no_chord_comes() {
+    let mut widget = widget();
+    let mut state = KeyBindingsState::new();
+    let timeout = Duration::from_millis(500);
+    state.select(MOVE_DOWN);
+    press(&mut state, &mut widget, KeyCode::Enter);
+    // nothing captured yet, waits as long as it takes
+    let late = Duration::from_secs(60);
+    assert_eq!(state.finish_capture_after(&mut widget, late, timeout), RebindOutcome::Ignored);
+
+    state.handle_rebind_key(&mut widget, KeyCode::Char('x'), KeyModifiers::empty(), late);
+    assert_eq!(
+        state.finish_capture_after(&mut widget, late + timeout / 2, timeout),
+        RebindOutcome::Ignored
+    );
+    assert_eq!(
+        state.finish_capture_after(&mut widget, late + timeout * 2, timeout),
+        RebindOutcome::Rebound
+    );
+    assert_eq!(state.rebind, RebindMode::Idle);
+    assert_eq!(action(&widget, "x").as_deref(), Some("Move down"));
+}
+
+#[test]
+fn a_used_key_is_a_conflict_until_resolved() {
+    let mut widget = widget();
+    let mut state = conflict_on(&mut widget, KeyCode::Char('w'));
+    // other keys don't resolve it
+    assert_eq!(press(&mut state, &mut widget, KeyCode::Char('x')), RebindOutcome::Handled);
+    assert!(matches!(state.rebind, RebindMode::Conflict { .. }));
+
+    assert_eq!(press(&mut state, &mut widget, KeyCode::Esc), RebindOutcome::Handled);
+    assert_eq!(state.rebind, RebindMode::Idle);
+    assert_eq!(action(&widget, "w").as_deref(), Some("Move up"));
+    assert_eq!(action(&widget, "s").as_deref(), Some("Move down"));
+}
+
+#[test]
+fn a_conflict_can_swap_the_keys() {
+    let mut widget = widget();
+    let mut state = conflict_on(&mut widget, KeyCode::Char('w'));
+    assert_eq!(press(&mut state, &mut widget, KeyCode::Char('s')), RebindOutcome::Rebound);
+    assert_eq!(state.rebind, RebindMode::Idle);
+    assert_eq!(action(&widget, "w").as_deref(), Some("Move down"));
+    assert_eq!(action(&widget, "s").as_deref(), Some("Move up"));
+}
+
+#[test]
+fn a_conflict_can_replace_the_other_binding() {
+    let mut widget = widget();
+    let mut state = conflict_on(&mut widget, KeyCode::Char('w'));
+    assert_eq!(press(&mut state, &mut widget, KeyCode::Enter), RebindOutcome::Rebound);
+    assert_eq!(state.rebind, RebindMode::Idle);
+    assert_eq!(action(&widget, "w").as_deref(), Some("Move down"));
+    // Move up falls back to its system key
+    assert_eq!(action(&widget, "Up").as_deref(), Some("Move up"));
+    assert_eq!(action(&widget, "s"), None);
+}
+
+#[test]
+fn replacing_is_refused_without_a_key_to_fall_back_to() {
+    let mut widget = widget();
+    let w = parse_key_sequence("w").unwrap();
+    let up = parse_key_sequence("Up").unwrap();
+    widget.rebind_user_key(&w, &up).unwrap();
+
+    let mut state = conflict_on(&mut widget, KeyCode::Up);
+    assert_eq!(press(&mut state, &mut widget, KeyCode::Enter), RebindOutcome::Handled);
+    assert!(matches!(state.rebind, RebindMode::Refused { .. }), "{:?}", state.rebind);
+    assert_eq!(action(&widget, "Up").as_deref(), Some("Move up"));
+    assert_eq!(action(&widget, "s").as_deref(), Some("Move down"));
+
+    // any key dismisses the refusal
+    assert_eq!(press(&mut state, &mut widget, KeyCode::Char('x')), RebindOutcome::Handled);
+    assert_eq!(state.rebind, RebindMode::Idle);
+}
+
+#[test]
+fn fixed_bindings_are_refused() {
+    let mut widget = widget();
+    let mut state = KeyBindingsState::new();
+    state.select(0);
+    press(&mut state, &mut widget, KeyCode::Enter);
+    assert_eq!(
+        state.rebind,
+        RebindMode::Refused {
+            reason: "'Quit' cannot be changed".to_string()
+        }
+    );
+
+    let q = parse_key_sequence("q").unwrap();
+    let s = parse_key_sequence("s").unwrap();
+    assert!(widget.swap_user_keys(&s, &q).is_err());
+    assert!(widget.replace_user_key(&s, &q).is_err());
+    assert!(widget.rebind_user_key(&q, &parse_key_sequence("x").unwrap()).is_err());
+    assert_eq!(action(&widget, "q").as_deref(), Some("Quit"));
+}
+
+#[test]
+fn swapping_or_replacing_a_key_with_itself_changes_nothing() {
+    let mut widget = widget();
+    let s = parse_key_sequence("s").unwrap();
+    widget.swap_user_keys(&s, &s).unwrap();
+    widget.replace_user_key(&s, &s).unwrap();
+    assert_eq!(widget.key_bindings.len(), 3);
+    assert_eq!(action(&widget, "s").as_deref(), Some("Move down"));
+}
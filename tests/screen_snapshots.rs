@@ -0,0 +1,113 @@
+mod common;
+
+use bevy_starter::{
+    config::key_bindings::parse_key_bindings,
+    mini_salsa::theme::IMPERIAL,
+    widgets::{
+        home::{home_menu, HomeWidget, TitleArt},
+        key_bindings::{KeyBindingsState, KeyBindingsView},
+        menu::MenuItemState,
+        options::{options_menu, OptionComponents, OptionsWidget},
+    },
+};
+use common::snapshot::{assert_snapshot, assert_styled_snapshot};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::StatefulWidgetRef,
+};
+
+const SIZES: [(u16, u16); 3] = [(40, 12), (80, 24), (120, 40)];
+
+const KEY_BINDINGS: &str = r#"
+[[binding]]
+action = "Quit"
+user_key = "q"
+system_key = "q"
+
+[[binding]]
+action = "Move up"
+user_key = "w"
+system_key = "Up"
+customizable = true
+
+[[binding]]
+action = "Move down"
+user_key = "s"
+system_key = "Down"
+customizable = true
+
+[[binding]]
+action = "Save"
+user_key = "Ctrl+s"
+system_key = "Ctrl+s"
+customizable = true
+"#;
+
+fn empty_buffer(width: u16, height: u16) -> Buffer {
+    Buffer::empty(Rect::new(0, 0, width, height))
+}
+
+#[test]
+fn home_screen() {
+    for (width, height) in SIZES {
+        let title_art = TitleArt::default();
+        let mut menu = home_menu();
+        menu.reset();
+        let mut buffer = empty_buffer(width, height);
+        HomeWidget {
+            theme: &IMPERIAL,
+            title: &title_art.unicode,
+            title_text: &title_art.text,
+        }
+        .render_ref(buffer.area, &mut buffer, &mut menu);
+        assert_snapshot(&format!("home_{}x{}", width, height), &buffer);
+    }
+}
+
+#[test]
+fn options_menu_item_states() {
+    let video = 2;
+    for item_state in [
+        MenuItemState::Normal,
+        MenuItemState::Focused,
+        MenuItemState::Hovered,
+        MenuItemState::Pressed,
+        MenuItemState::Disabled,
+    ] {
+        let mut menu = options_menu();
+        match item_state {
+            MenuItemState::Normal => {}
+            MenuItemState::Focused => menu.focused = Some(video),
+            MenuItemState::Hovered => menu.hovered = Some(video),
+            MenuItemState::Pressed => {
+                menu.hovered = Some(video);
+                menu.pressed = Some(video);
+            }
+            MenuItemState::Disabled => menu.set_enabled(OptionComponents::Video, false),
+        }
+        let mut buffer = empty_buffer(60, 30);
+        OptionsWidget { theme: &IMPERIAL }.render_ref(buffer.area, &mut buffer, &mut menu);
+        assert_eq!(menu.item_state(video), item_state);
+        let name = format!("options_{:?}", item_state).to_lowercase();
+        assert_styled_snapshot(&name, &buffer);
+    }
+}
+
+#[test]
+fn key_bindings_screen() {
+    let loaded = parse_key_bindings(KEY_BINDINGS).unwrap();
+    assert!(loaded.errors.is_empty(), "{:?}", loaded.errors);
+    for (width, height) in SIZES {
+        let mut state = KeyBindingsState::new();
+        state.select(1);
+        let mut buffer = empty_buffer(width, height);
+        KeyBindingsView {
+            key_bindings: &loaded.widget,
+            profile: None,
+            theme: &IMPERIAL,
+        }
+        .render_ref(buffer.area, &mut buffer, &mut state);
+        assert_snapshot(&format!("key_bindings_{}x{}", width, height), &buffer);
+    }
+}
@@ -0,0 +1,322 @@
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use bevy::prelude::*;
+use bevy_starter::{
+    config::{
+        key_bindings::{BuiltinProfile, ControlSettings, KeyBindingEntry},
+        settings::{
+            load_settings_file, parse_settings, save_settings_file, settings_changed_any,
+            Settings, SettingsChanged, SettingsPath, SettingsPlugin, SettingsSection,
+            SETTINGS_VERSION,
+        },
+        theme::Theme,
+        video::BorderSet,
+    },
+    mini_salsa::theme::HIGH_CONTRAST,
+    widgets::key_bindings::{KeyBindingsWidget, KeySequence},
+};
+use common::TestApp;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// An empty directory for a test's files.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bevy_starter-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn jump_binding() -> KeyBindingEntry {
+    KeyBindingEntry {
+        action: "Jump".to_string(),
+        user_key: "j".to_string(),
+        system_key: "Space".to_string(),
+        customizable: true,
+    }
+}
+
+#[test]
+fn missing_fields_keep_their_defaults() {
+    let loaded = parse_settings(
+        r#"
+        version = 1
+
+        [video]
+        border_set = "ascii"
+
+        [audio]
+        music_volume = 250
+        "#,
+    )
+    .unwrap();
+    let defaults = Settings::default();
+    assert!(!loaded.was_migrated());
+    assert_eq!(loaded.settings.video.border_set, BorderSet::Ascii);
+    assert_eq!(loaded.settings.video.frame_rate, defaults.video.frame_rate);
+    // out of range values are clamped
+    assert_eq!(loaded.settings.audio.music_volume, 100);
+    assert_eq!(loaded.settings.theme, defaults.theme);
+    assert_eq!(loaded.settings.accessibility, defaults.accessibility);
+}
+
+#[test]
+fn a_bad_value_only_resets_itself() {
+    let loaded = parse_settings(
+        r#"
+        accessibility = 5
+
+        [video]
+        border_set = "ascii"
+        frame_rate = "fast"
+
+        [audio]
+        master_volume = 40
+        music_volume = 300
+        "#,
+    )
+    .unwrap();
+    let defaults = Settings::default();
+    assert_eq!(loaded.settings.video.border_set, BorderSet::Ascii);
+    assert_eq!(loaded.settings.video.frame_rate, defaults.video.frame_rate);
+    assert_eq!(loaded.settings.audio.master_volume, 40);
+    assert_eq!(loaded.settings.audio.music_volume, defaults.audio.music_volume);
+    assert_eq!(loaded.settings.accessibility, defaults.accessibility);
+
+    let errors = &loaded.errors;
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors[0].starts_with("[video] frame_rate:"), "{}", errors[0]);
+    assert!(errors[1].starts_with("[audio] music_volume:"), "{}", errors[1]);
+    assert!(errors[2].starts_with("[accessibility]: expected a table"), "{}", errors[2]);
+}
+
+#[test]
+fn an_unreadable_file_is_backed_up_before_it_is_replaced() {
+    let path = test_dir("unreadable").join("settings.toml");
+    let contents = "version = 1
+[audio
+master_volume = 40
+";
+    fs::write(&path, contents).unwrap();
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(SettingsPath(Some(path.clone())))
+        .add_plugins(SettingsPlugin);
+    app.update();
+    assert_eq!(*app.world().resource::<Settings>(), Settings::default());
+
+    app.world_mut().resource_mut::<Settings>().audio.sfx_muted = true;
+    app.update();
+    assert_eq!(fs::read_to_string(path.with_extension("toml.bak")).unwrap(), contents);
+    assert!(load_settings_file(&path).unwrap().settings.audio.sfx_muted);
+}
+
+#[test]
+fn version_0_moves_the_scheme_out_of_the_video_settings() {
+    let loaded = parse_settings(
+        r#"
+        version = 0
+
+        [video]
+        frame_rate = 30
+        theme = "Light"
+
+        [[controls.binding]]
+        action = "Jump"
+        user_key = "j"
+        system_key = "Space"
+        customizable = true
+
+        [[controls.binding]]
+        action = "Broken"
+        "#,
+    )
+    .unwrap();
+    assert!(loaded.was_migrated());
+    assert_eq!(loaded.settings.video.frame_rate, 30);
+    assert_eq!(loaded.settings.theme.name, "Light");
+    assert_eq!(loaded.settings.controls.bindings, [jump_binding()]);
+    assert_eq!(loaded.errors.len(), 1, "{:?}", loaded.errors);
+}
+
+#[test]
+fn newer_versions_are_rejected() {
+    let contents = format!("version = {}", SETTINGS_VERSION + 1);
+    let err = parse_settings(&contents).unwrap_err();
+    assert!(format!("{:#}", err).contains("newer"), "{:#}", err);
+}
+
+#[test]
+fn settings_survive_a_save_and_load() {
+    let path = test_dir("round-trip").join("settings.toml");
+    let mut settings = Settings::default();
+    settings.video.border_set = BorderSet::Ascii;
+    settings.audio.sfx_muted = true;
+    settings.accessibility.reduced_motion = true;
+    settings.theme.name = "Light".to_string();
+    settings.controls.bindings = vec![jump_binding()];
+    settings.controls.save_profile("Jumpy").unwrap();
+    save_settings_file(&path, &settings).unwrap();
+
+    let contents = fs::read_to_string(&path).unwrap();
+    assert!(contents.starts_with(&format!("version = {}", SETTINGS_VERSION)), "{}", contents);
+    let loaded = load_settings_file(&path).unwrap();
+    assert!(!loaded.was_migrated());
+    assert_eq!(loaded.settings, settings);
+}
+
+#[test]
+fn the_separate_files_are_migrated() {
+    let dir = test_dir("split-files");
+    fs::write(dir.join("video.toml"), "frame_rate = 144\ntheme = \"Monochrome\"\n").unwrap();
+    fs::write(dir.join("audio.toml"), "master_volume = 10\n").unwrap();
+    fs::write(
+        dir.join("key_bindings.toml"),
+        "[[binding]]\naction = \"Jump\"\nuser_key = \"j\"\nsystem_key = \"Space\"\ncustomizable = true\n",
+    )
+    .unwrap();
+
+    let loaded = load_settings_file(&dir.join("settings.toml")).unwrap();
+    assert_eq!(loaded.version, 0);
+    assert_eq!(loaded.settings.video.frame_rate, 144);
+    assert_eq!(loaded.settings.audio.master_volume, 10);
+    assert_eq!(loaded.settings.theme.name, "Monochrome");
+    assert_eq!(loaded.settings.controls.bindings, [jump_binding()]);
+
+    let loaded = load_settings_file(&test_dir("no-files").join("settings.toml")).unwrap();
+    assert!(!loaded.was_migrated());
+    assert_eq!(loaded.settings, Settings::default());
+}
+
+#[test]
+fn changes_are_announced_per_section() {
+    let mut app = TestApp::new();
+    let mut cursor = app.resource::<Events<SettingsChanged>>().get_cursor_current();
+    app.resource_mut::<Settings>().accessibility.high_contrast = true;
+    app.resource_mut::<Settings>().audio.music_muted = true;
+    app.update();
+
+    let events = app.resource::<Events<SettingsChanged>>();
+    let sections: Vec<SettingsSection> = cursor
+        .read(events)
+        .map(|changed| changed.section)
+        .collect();
+    assert_eq!(sections, [SettingsSection::Audio, SettingsSection::Accessibility]);
+    assert_eq!(app.resource::<Theme>().scheme, HIGH_CONTRAST);
+}
+
+#[test]
+fn a_system_on_several_sections_runs_once_per_change() {
+    #[derive(Resource, Default)]
+    struct Runs(u32);
+
+    let mut app = App::new();
+    app.add_event::<SettingsChanged>()
+        .init_resource::<Runs>()
+        .add_systems(
+            Update,
+            (|mut runs: ResMut<Runs>| runs.0 += 1).run_if(settings_changed_any(&[
+                SettingsSection::Video,
+                SettingsSection::Accessibility,
+            ])),
+        );
+    app.world_mut().send_event(SettingsChanged {
+        section: SettingsSection::Video,
+    });
+    app.world_mut().send_event(SettingsChanged {
+        section: SettingsSection::Accessibility,
+    });
+    app.update();
+    app.update();
+    assert_eq!(app.world().resource::<Runs>().0, 1);
+
+    app.world_mut().send_event(SettingsChanged {
+        section: SettingsSection::Audio,
+    });
+    app.update();
+    assert_eq!(app.world().resource::<Runs>().0, 1);
+}
+
+#[test]
+fn key_bindings_follow_the_controls() {
+    let mut app = TestApp::new();
+    app.resource_mut::<Settings>().controls.bindings.push(jump_binding());
+    app.update();
+    let key_bindings = app.resource::<KeyBindingsWidget>();
+    let j = KeySequence::from((KeyCode::Char('j'), KeyModifiers::empty()));
+    let jump = &key_bindings.key_bindings[&j];
+    assert_eq!(jump.system_key, KeyCode::Char(' '));
+}
+
+#[test]
+fn user_profiles_are_read_with_the_controls() {
+    let loaded = parse_settings(
+        r#"
+        [[controls.profile]]
+        name = "Jumpy"
+
+        [[controls.profile.binding]]
+        action = "Jump"
+        user_key = "j"
+        system_key = "Space"
+        customizable = true
+
+        [[controls.profile.binding]]
+        action = "Broken"
+        user_key = "Nope+x"
+
+        [[controls.profile]]
+        binding = []
+        "#,
+    )
+    .unwrap();
+    let controls = &loaded.settings.controls;
+    // no bindings of their own, so the default profile
+    assert_eq!(controls.active_profile().as_deref(), Some("Arrows"));
+    assert_eq!(controls.profile("Jumpy"), Some(vec![jump_binding()]));
+    assert_eq!(loaded.errors.len(), 2, "{:?}", loaded.errors);
+}
+
+#[test]
+fn built_in_profile_names_are_reserved() {
+    let mut controls = ControlSettings::default();
+    assert!(controls.save_profile("vim").is_err());
+    assert!(controls.save_profile("  ").is_err());
+    controls.bindings = BuiltinProfile::Vim.bindings();
+    controls.save_profile("Mine").unwrap();
+    controls.bindings = vec![jump_binding()];
+    controls.save_profile("MINE").unwrap();
+    assert_eq!(controls.profiles.len(), 1);
+    assert_eq!(controls.profile("mine"), Some(vec![jump_binding()]));
+    assert_eq!(controls.active_profile().as_deref(), Some("MINE"));
+}
+
+#[test]
+fn loaded_profiles_named_like_another_are_reported() {
+    let loaded = parse_settings(
+        r#"
+        [[controls.profile]]
+        name = "vim"
+
+        [[controls.profile.binding]]
+        action = "Jump"
+        user_key = "j"
+        system_key = "Space"
+
+        [[controls.profile]]
+        name = "Jumpy"
+
+        [[controls.profile]]
+        name = "JUMPY"
+        "#,
+    )
+    .unwrap();
+    let controls = &loaded.settings.controls;
+    assert_eq!(controls.profiles.len(), 1);
+    assert_eq!(controls.profile("vim"), Some(BuiltinProfile::Vim.bindings()));
+    let errors = &loaded.errors;
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(errors[0].contains("named like the built-in profile `Vim`"), "{}", errors[0]);
+    assert!(errors[1].starts_with("profile `JUMPY`: defined twice"), "{}", errors[1]);
+}
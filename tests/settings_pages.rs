@@ -0,0 +1,170 @@
+mod common;
+
+use bevy_starter::{
+    config::{
+        audio::AudioSettings,
+        key_bindings::{ControlSettings, KeyBindingEntry},
+        settings::Settings,
+    },
+    states::screen::Screen,
+    widgets::{
+        dialog::Dialogs,
+        key_bindings::{KeyBindingsWidget, KeySequence},
+        settings_page::{SettingsDraft, DISCARD_CHANGES_DIALOG},
+    },
+};
+use common::TestApp;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+#[test]
+fn changes_are_kept_in_the_draft_until_applied() {
+    let mut app = TestApp::new();
+    let volume = app.resource::<Settings>().audio.master_volume;
+    app.press(KeyCode::Esc).click_text("Audio").press(KeyCode::Left);
+    assert_eq!(app.resource::<SettingsDraft>().audio.master_volume, volume - 5);
+    assert_eq!(app.resource::<Settings>().audio.master_volume, volume);
+
+    app.click_text("Apply");
+    assert_eq!(app.resource::<Settings>().audio.master_volume, volume - 5);
+    // nothing left to discard
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Options);
+}
+
+#[test]
+fn the_ends_of_a_volume_slider_are_silence_and_full_volume() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc).click_text("Audio");
+    // the master volume is the first slider
+    let (first, row) = app.find_text("█").unwrap();
+    app.click(first, row);
+    assert_eq!(app.resource::<SettingsDraft>().audio.master_volume, 0);
+
+    let width = app.screen().lines().nth(row as usize).unwrap().matches('░').count() as u16;
+    app.click(first + width - 1, row);
+    assert_eq!(app.resource::<SettingsDraft>().audio.master_volume, 100);
+}
+
+#[test]
+fn leaving_with_changes_asks_first() {
+    let mut app = TestApp::new();
+    let settings = app.resource::<Settings>().clone();
+    app.press(KeyCode::Esc).click_text("Video").press(KeyCode::Right);
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Video);
+    let dialogs = app.resource::<Dialogs>();
+    assert_eq!(dialogs.top().map(|dialog| dialog.id), Some(DISCARD_CHANGES_DIALOG));
+
+    // No keeps editing
+    app.click_text("No");
+    assert_eq!(app.state(), Screen::Video);
+    assert_ne!(app.resource::<SettingsDraft>().0, settings);
+
+    app.click_text("Back").click_text("Yes");
+    assert_eq!(app.state(), Screen::Options);
+    assert_eq!(*app.resource::<Settings>(), settings);
+}
+
+#[test]
+fn reset_to_defaults_only_touches_its_page() {
+    let mut app = TestApp::new();
+    app.resource_mut::<Settings>().audio.master_volume = 10;
+    app.resource_mut::<Settings>().theme.name = "Light".to_string();
+    app.press(KeyCode::Esc).click_text("Audio").click_text("Reset to defaults");
+    assert_eq!(app.resource::<SettingsDraft>().audio, AudioSettings::default());
+    assert_eq!(app.resource::<Settings>().audio.master_volume, 10);
+
+    app.click_text("Apply");
+    let settings = app.resource::<Settings>();
+    assert_eq!(settings.audio, AudioSettings::default());
+    assert_eq!(settings.theme.name, "Light");
+}
+
+#[test]
+fn rebinding_applies_to_the_bindings_in_use() {
+    let mut app = TestApp::new();
+    app.resource_mut::<Settings>().controls.bindings = vec![KeyBindingEntry {
+        action: "Jump".to_string(),
+        user_key: "j".to_string(),
+        system_key: "Space".to_string(),
+        customizable: true,
+    }];
+    app.press(KeyCode::Esc).click_text("Key Bindings");
+    app.press(KeyCode::Enter).press(KeyCode::Char('k')).press(KeyCode::Enter);
+    let in_use = |app: &TestApp, key: char| {
+        app.resource::<KeyBindingsWidget>()
+            .key_bindings
+            .contains_key(&KeySequence::from((KeyCode::Char(key), KeyModifiers::empty())))
+    };
+    assert_eq!(app.resource::<SettingsDraft>().controls.bindings[0].user_key, "k");
+    assert!(in_use(&app, 'j'));
+
+    // back to the default profile
+    app.press(KeyCode::Char('r'));
+    assert_eq!(
+        app.resource::<SettingsDraft>().controls.active_profile().as_deref(),
+        Some("Arrows")
+    );
+    app.press(KeyCode::Char('a'));
+    assert!(!in_use(&app, 'j'));
+    assert!(!in_use(&app, 'k'));
+    assert_eq!(app.resource::<Settings>().controls.bindings, ControlSettings::default().bindings);
+}
+
+#[test]
+fn apply_keeps_the_sections_of_other_pages() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc).click_text("Audio").press(KeyCode::Left);
+    // changed elsewhere while the page is open
+    app.resource_mut::<Settings>().accessibility.high_contrast = true;
+    app.update();
+    assert!(!app.resource::<SettingsDraft>().accessibility.high_contrast);
+
+    app.click_text("Apply");
+    let settings = app.resource::<Settings>();
+    assert!(settings.accessibility.high_contrast);
+    assert_eq!(settings.audio, app.resource::<SettingsDraft>().audio);
+    // the other section is no reason to ask before leaving
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Options);
+}
+
+#[test]
+fn profiles_are_picked_on_the_key_bindings_screen() {
+    let mut app = TestApp::new();
+    assert_eq!(app.resource::<KeyBindingsWidget>().key_bindings.len(), 7);
+    app.press(KeyCode::Esc).click_text("Key Bindings");
+    assert!(app.find_text("Key Bindings (Arrows)").is_some(), "{}", app.screen());
+
+    app.press(KeyCode::Char('p'));
+    assert!(app.find_text("Key Bindings (WASD)").is_some(), "{}", app.screen());
+    app.press(KeyCode::Char('a'));
+    assert_eq!(
+        app.resource::<Settings>().controls.active_profile().as_deref(),
+        Some("WASD")
+    );
+    let key_bindings = app.resource::<KeyBindingsWidget>();
+    let w = KeySequence::from((KeyCode::Char('w'), KeyModifiers::empty()));
+    let up = &key_bindings.key_bindings[&w];
+    assert_eq!(up.system_key, KeyCode::Up);
+}
+
+#[test]
+fn bindings_are_saved_as_a_named_profile() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc).click_text("Key Bindings");
+    app.press(KeyCode::Char('p')).press(KeyCode::Char('n'));
+    // the keys of the name are not taken as commands or movement
+    for c in "wasd pad".chars() {
+        app.press(KeyCode::Char(c));
+    }
+    assert!(app.find_text("Profile name: wasd pad_").is_some(), "{}", app.screen());
+    app.press(KeyCode::Enter);
+
+    let controls = &app.resource::<SettingsDraft>().controls;
+    assert_eq!(controls.profiles.len(), 1);
+    assert_eq!(controls.profiles[0].name, "wasd pad");
+    assert_eq!(controls.profile("wasd pad"), controls.profile("WASD"));
+    assert_eq!(app.resource::<Settings>().controls.profiles, []);
+    assert_eq!(app.state(), Screen::KeyBindings);
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_starter::{
+    config::{key_bindings::KeyBindingEntry, settings::Settings},
+    input::routing::InputCapture,
+    states::{
+        app_state::AppState,
+        menu_screen::MenuScreen,
+        navigation::{Navigate, ScreenStack},
+        screen::Screen,
+    },
+    widgets::{
+        game::GameSession,
+        splash::SplashDuration,
+    },
+};
+use common::TestApp;
+use crossterm::event::KeyCode;
+
+#[test]
+fn any_key_skips_the_splash_screen() {
+    let mut app = TestApp::new();
+    app.resource_mut::<SplashDuration>().0 = Duration::from_secs(60);
+    app.resource_mut::<NextState<AppState>>().set(AppState::Splash);
+    app.update();
+    assert_eq!(app.state(), Screen::Splash);
+    assert!(app.find_text("Press any key").is_some(), "{}", app.screen());
+
+    app.update();
+    assert_eq!(app.state(), Screen::Splash);
+    app.press(KeyCode::Char('x'));
+    assert_eq!(app.state(), Screen::Home);
+}
+
+#[test]
+fn pausing_keeps_the_game_and_the_main_menu_ends_it() {
+    let mut app = TestApp::new();
+    app.click_text("New Game");
+    assert_eq!(app.state(), Screen::Game);
+    assert!(app.app.world().contains_resource::<GameSession>());
+
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Pause);
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Game);
+    assert!(app.app.world().contains_resource::<GameSession>());
+
+    app.press(KeyCode::Esc).click_text("Main Menu").click_text("Yes");
+    assert_eq!(app.state(), Screen::Home);
+    assert!(!app.app.world().contains_resource::<GameSession>());
+}
+
+#[test]
+fn options_opened_while_paused_return_to_the_pause_menu() {
+    let mut app = TestApp::new();
+    app.click_text("New Game").press(KeyCode::Esc).click_text("Options");
+    assert_eq!(app.state(), Screen::Options);
+    assert_eq!(app.app_state(), AppState::Paused);
+
+    app.click_text("Video");
+    assert_eq!(app.state(), Screen::Video);
+    app.press(KeyCode::Esc).press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Pause);
+}
+
+#[test]
+fn menu_screens_reset_when_their_parent_exits() {
+    let mut app = TestApp::new();
+    app.click_text("New Game").press(KeyCode::Esc).click_text("Options");
+    assert_eq!(*app.resource::<State<MenuScreen>>().get(), MenuScreen::Options);
+
+    // leaving the paused state drops the sub-state, pausing again starts at the pause menu
+    app.resource_mut::<NextState<AppState>>().set(AppState::InGame);
+    app.update();
+    assert!(app.app.world().get_resource::<State<MenuScreen>>().is_none());
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Pause);
+}
+
+#[test]
+fn the_screen_stack_pushes_pops_and_replaces() {
+    let mut stack = ScreenStack::default();
+    assert_eq!(stack.current(), MenuScreen::Main);
+    stack.apply(Navigate::Pop);
+    assert!(stack.is_root());
+
+    stack.apply(Navigate::Push(MenuScreen::Options));
+    stack.apply(Navigate::Push(MenuScreen::Video));
+    assert_eq!(stack.pages(), [MenuScreen::Options, MenuScreen::Video]);
+    stack.apply(Navigate::Replace(MenuScreen::Audio));
+    assert_eq!(stack.current(), MenuScreen::Audio);
+    stack.apply(Navigate::Pop);
+    assert_eq!(stack.current(), MenuScreen::Options);
+
+    stack.apply(Navigate::Push(MenuScreen::KeyBindings));
+    stack.apply(Navigate::Clear);
+    assert!(stack.is_root());
+}
+
+#[test]
+fn back_returns_to_where_a_screen_was_opened_from() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc).click_text("Audio");
+    assert_eq!(app.state(), Screen::Audio);
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Options);
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Home);
+    assert!(app.resource::<ScreenStack>().is_root());
+
+    app.send_event(Navigate::Push(MenuScreen::Options));
+    app.send_event(Navigate::Push(MenuScreen::Video));
+    app.update();
+    assert_eq!(app.state(), Screen::Video);
+    app.send_event(Navigate::Clear);
+    app.update();
+    assert_eq!(app.state(), Screen::Home);
+}
+
+#[test]
+fn escape_cancels_a_rebind_before_leaving_key_bindings() {
+    let mut app = TestApp::new();
+    app.resource_mut::<Settings>()
+        .controls
+        .bindings
+        .push(KeyBindingEntry {
+            action: "Jump".to_string(),
+            user_key: "j".to_string(),
+            system_key: "Space".to_string(),
+            customizable: true,
+        });
+    app.press(KeyCode::Esc).click_text("Key Bindings");
+    app.press(KeyCode::Enter);
+    assert!(app.resource::<InputCapture>().0);
+
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::KeyBindings);
+    assert!(!app.resource::<InputCapture>().0);
+    app.press(KeyCode::Esc);
+    assert_eq!(app.state(), Screen::Options);
+}
+
+#[test]
+fn the_stack_is_emptied_when_the_menus_are_left() {
+    let mut app = TestApp::new();
+    app.click_text("New Game").press(KeyCode::Esc).click_text("Options");
+    assert_eq!(app.resource::<ScreenStack>().pages(), [MenuScreen::Options]);
+
+    app.resource_mut::<NextState<AppState>>().set(AppState::InGame);
+    app.update().update();
+    assert!(app.resource::<ScreenStack>().is_root());
+}
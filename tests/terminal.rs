@@ -0,0 +1,65 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use bevy_starter::{
+    config::settings::Settings,
+    terminal::context::TerminalContext,
+    widgets::video::FrameRateLimit,
+};
+use common::TestApp;
+use ratatui::{buffer::Buffer, style::Style};
+
+fn first_line(context: &TerminalContext) -> String {
+    let buffer: &Buffer = context.test_buffer().unwrap();
+    (0..buffer.area.width)
+        .map(|x| buffer[(x, 0)].symbol())
+        .collect()
+}
+
+#[test]
+fn the_draws_of_an_update_are_shown_as_one_frame() {
+    let mut context = TerminalContext::test(10, 1);
+    context
+        .draw(|frame| {
+            frame.buffer_mut().set_string(0, 0, "screen", Style::default());
+        })
+        .unwrap();
+    // an overlay, on top of the screen
+    context
+        .draw(|frame| {
+            frame.buffer_mut().set_string(4, 0, "!!", Style::default());
+        })
+        .unwrap();
+    assert_eq!(first_line(&context), " ".repeat(10));
+
+    context.present().unwrap();
+    assert_eq!(first_line(&context), "scre!!    ");
+    // nothing drawn, nothing changes
+    context.present().unwrap();
+    assert_eq!(first_line(&context), "scre!!    ");
+
+    // the next frame starts empty
+    context
+        .draw(|frame| {
+            frame.buffer_mut().set_string(0, 0, "next", Style::default());
+        })
+        .unwrap();
+    context.present().unwrap();
+    assert_eq!(first_line(&context), "next      ");
+}
+
+#[test]
+fn frames_are_held_back_to_the_frame_rate() {
+    let mut app = TestApp::new();
+    app.resource_mut::<Settings>().video.frame_rate = 30;
+    app.resource_mut::<FrameRateLimit>().0 = true;
+    app.update();
+
+    let start = Instant::now();
+    for _ in 0..5 {
+        app.update();
+    }
+    // the first of them may be shorter, its frame started before the limit was on
+    assert!(start.elapsed() >= Duration::from_secs(4) / 30, "{:?}", start.elapsed());
+}
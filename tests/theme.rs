@@ -0,0 +1,89 @@
+mod common;
+
+use bevy_starter::{
+    config::{
+        settings::Settings,
+        theme::{parse_schemes, Theme, ThemeRegistry},
+    },
+    mini_salsa::theme::{Scheme, IMPERIAL, LIGHT},
+    states::screen::Screen,
+};
+use common::TestApp;
+use crossterm::event::KeyCode;
+use ratatui::style::Color;
+
+#[test]
+fn the_colors_row_switches_the_theme_on_apply() {
+    let mut app = TestApp::new();
+    app.press(KeyCode::Esc).click_text("Video");
+    assert_eq!(app.state(), Screen::Video);
+    assert_eq!(app.resource::<Theme>().name, "Dark");
+
+    let (column, row) = app.find_text("Colors").unwrap();
+    app.click(column, row);
+    assert_eq!(app.resource::<Theme>().name, "Dark");
+    app.click_text("Apply");
+    assert_eq!(app.resource::<Settings>().theme.name, "Light");
+    assert_eq!(app.resource::<Theme>().scheme, LIGHT);
+    // the border is drawn with the block style of the new scheme
+    assert_eq!(app.buffer()[(0, 0)].bg, LIGHT.block().bg.unwrap());
+}
+
+#[test]
+fn unknown_scheme_falls_back_to_the_default() {
+    let mut app = TestApp::new();
+    app.resource_mut::<Settings>().theme.name = "Nope".to_string();
+    app.update();
+    assert_eq!(app.resource::<Theme>().scheme, IMPERIAL);
+}
+
+#[test]
+fn custom_schemes_are_registered() {
+    let mut app = TestApp::new();
+    let mut ocean = IMPERIAL;
+    ocean.primary = Scheme::linear4(0x003a57, 0x00a2fd);
+    app.resource_mut::<ThemeRegistry>().insert("Ocean", ocean.clone());
+    app.resource_mut::<Settings>().theme.name = "Ocean".to_string();
+    app.update();
+    assert_eq!(app.resource::<Theme>().scheme, ocean);
+}
+
+#[test]
+fn schemes_file_uses_base_and_top_colors() {
+    let loaded = parse_schemes(
+        r#"
+        [[scheme]]
+        name = "Ocean"
+        primary = [0x003a57, 0x00a2fd]
+        black = [0x000000, 0x303030]
+
+        [[scheme]]
+        name = "Broken"
+        purpel = [0x000000, 0xffffff]
+
+        [[scheme]]
+        primary = [0x000000, 0xffffff]
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(loaded.schemes.len(), 1);
+    let (name, scheme) = &loaded.schemes[0];
+    assert_eq!(name, "Ocean");
+    assert_eq!(scheme.primary, Scheme::linear4(0x003a57, 0x00a2fd));
+    assert_eq!(scheme.black[0], Color::Rgb(0, 0, 0));
+    assert_eq!(scheme.secondary, IMPERIAL.secondary);
+
+    assert_eq!(loaded.errors.len(), 2, "{:?}", loaded.errors);
+    assert!(loaded.errors[0].starts_with("scheme #2: unknown palette `purpel`"));
+    assert!(loaded.errors[1].starts_with("scheme #3:"));
+}
+
+#[test]
+fn light_schemes_pick_dark_text() {
+    assert!(LIGHT.is_light());
+    assert!(!IMPERIAL.is_light());
+    assert_eq!(LIGHT.text_color(LIGHT.black[0]), LIGHT.white[3]);
+    assert_eq!(IMPERIAL.text_color(IMPERIAL.black[0]), IMPERIAL.white[3]);
+    assert_eq!(IMPERIAL.text_color(Color::White), IMPERIAL.black[0]);
+}